@@ -0,0 +1,11 @@
+DROP TRIGGER IF EXISTS set_updated_at ON public.todos;
+DROP TRIGGER IF EXISTS set_updated_at ON public.users;
+
+ALTER TABLE public.todos
+  DROP COLUMN completed_at ,
+  DROP COLUMN updated_at ,
+  DROP COLUMN created_at ;
+
+ALTER TABLE public.users
+  DROP COLUMN updated_at ,
+  DROP COLUMN created_at ;
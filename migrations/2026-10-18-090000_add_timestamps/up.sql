@@ -0,0 +1,13 @@
+ALTER TABLE public.users
+  ADD COLUMN created_at timestamptz DEFAULT now() NOT NULL ,
+  ADD COLUMN updated_at timestamptz DEFAULT now() NOT NULL ;
+
+ALTER TABLE public.todos
+  ADD COLUMN created_at timestamptz DEFAULT now() NOT NULL ,
+  ADD COLUMN updated_at timestamptz DEFAULT now() NOT NULL ,
+  ADD COLUMN completed_at timestamptz ;
+
+UPDATE public.todos SET completed_at = now() WHERE "checked" = true;
+
+SELECT diesel_manage_updated_at('public.users');
+SELECT diesel_manage_updated_at('public.todos');
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_todos_user_id_due_at;
+
+ALTER TABLE public.todos DROP COLUMN due_at;
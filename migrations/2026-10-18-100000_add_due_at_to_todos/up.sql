@@ -0,0 +1,3 @@
+ALTER TABLE public.todos ADD COLUMN due_at timestamptz ;
+
+CREATE INDEX idx_todos_user_id_due_at ON public.todos ( user_id, due_at );
@@ -0,0 +1 @@
+ALTER TABLE public.todos DROP COLUMN reminded_at;
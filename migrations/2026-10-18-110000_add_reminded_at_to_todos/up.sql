@@ -0,0 +1 @@
+ALTER TABLE public.todos ADD COLUMN reminded_at timestamptz ;
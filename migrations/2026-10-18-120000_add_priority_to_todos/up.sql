@@ -0,0 +1,4 @@
+-- Priority levels are stored as: 0 low, 1 normal, 2 high, 3 urgent
+ALTER TABLE public.todos ADD COLUMN priority smallint DEFAULT 1 NOT NULL ;
+
+CREATE INDEX idx_todos_user_id_priority ON public.todos ( user_id, priority );
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_todos_user_id_position;
+
+ALTER TABLE public.todos DROP COLUMN "position";
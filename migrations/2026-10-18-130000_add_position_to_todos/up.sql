@@ -0,0 +1,12 @@
+-- Positions are fractional so a todo can be moved between two others
+-- without renumbering the rest of the list
+ALTER TABLE public.todos ADD COLUMN "position" double precision DEFAULT 0 NOT NULL ;
+
+UPDATE public.todos t SET "position" = ordered.row_number
+FROM (
+  SELECT id, row_number() OVER ( PARTITION BY user_id ORDER BY created_at, id ) AS row_number
+  FROM public.todos
+) ordered
+WHERE t.id = ordered.id;
+
+CREATE INDEX idx_todos_user_id_position ON public.todos ( user_id, "position" );
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_todos_list_id;
+
+ALTER TABLE public.todos DROP CONSTRAINT IF EXISTS fk_todos_lists;
+ALTER TABLE public.todos DROP COLUMN list_id;
+
+DROP TABLE public.lists;
@@ -0,0 +1,19 @@
+CREATE TABLE public.lists
+(
+  id varchar(36) DEFAULT uuid_generate_v4() NOT NULL ,
+  user_id varchar(36) NOT NULL ,
+  name varchar(255) NOT NULL ,
+  created_at timestamptz DEFAULT now() NOT NULL ,
+  updated_at timestamptz DEFAULT now() NOT NULL ,
+  CONSTRAINT pk_lists_id PRIMARY KEY ( id ) ,
+  CONSTRAINT fk_lists_users FOREIGN KEY ( user_id ) REFERENCES public.users( id ) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_lists_user_id ON public.lists ( user_id );
+
+SELECT diesel_manage_updated_at('public.lists');
+
+ALTER TABLE public.todos ADD COLUMN list_id varchar(36) ;
+ALTER TABLE public.todos ADD CONSTRAINT fk_todos_lists FOREIGN KEY ( list_id ) REFERENCES public.lists( id ) ON DELETE SET NULL;
+
+CREATE INDEX idx_todos_list_id ON public.todos ( list_id );
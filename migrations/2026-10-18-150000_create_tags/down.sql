@@ -0,0 +1,2 @@
+DROP TABLE public.todo_tags;
+DROP TABLE public.tags;
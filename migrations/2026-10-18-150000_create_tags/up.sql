@@ -0,0 +1,21 @@
+CREATE TABLE public.tags
+(
+  id varchar(36) DEFAULT uuid_generate_v4() NOT NULL ,
+  user_id varchar(36) NOT NULL ,
+  name varchar(64) NOT NULL ,
+  created_at timestamptz DEFAULT now() NOT NULL ,
+  CONSTRAINT pk_tags_id PRIMARY KEY ( id ) ,
+  CONSTRAINT fk_tags_users FOREIGN KEY ( user_id ) REFERENCES public.users( id ) ON DELETE CASCADE ,
+  CONSTRAINT uq_tags_user_id_name UNIQUE ( user_id, name )
+);
+
+CREATE TABLE public.todo_tags
+(
+  todo_id varchar(36) NOT NULL ,
+  tag_id varchar(36) NOT NULL ,
+  CONSTRAINT pk_todo_tags PRIMARY KEY ( todo_id, tag_id ) ,
+  CONSTRAINT fk_todo_tags_todos FOREIGN KEY ( todo_id ) REFERENCES public.todos( id ) ON DELETE CASCADE ,
+  CONSTRAINT fk_todo_tags_tags FOREIGN KEY ( tag_id ) REFERENCES public.tags( id ) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_todo_tags_tag_id ON public.todo_tags ( tag_id );
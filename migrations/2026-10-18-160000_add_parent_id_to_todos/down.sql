@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_todos_parent_id;
+
+ALTER TABLE public.todos DROP CONSTRAINT IF EXISTS fk_todos_todos;
+ALTER TABLE public.todos DROP COLUMN parent_id;
@@ -0,0 +1,4 @@
+ALTER TABLE public.todos ADD COLUMN parent_id varchar(36) ;
+ALTER TABLE public.todos ADD CONSTRAINT fk_todos_todos FOREIGN KEY ( parent_id ) REFERENCES public.todos( id ) ON DELETE CASCADE;
+
+CREATE INDEX idx_todos_parent_id ON public.todos ( parent_id );
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS uq_todos_series_id_due_at;
+
+ALTER TABLE public.todos
+  DROP COLUMN series_id ,
+  DROP COLUMN recurrence ;
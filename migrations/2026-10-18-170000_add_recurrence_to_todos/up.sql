@@ -0,0 +1,7 @@
+-- All occurrences of the recurring todo share the series id, which is
+-- the id of the first occurrence, and each of them is due at a different time
+ALTER TABLE public.todos
+  ADD COLUMN recurrence varchar(255) ,
+  ADD COLUMN series_id varchar(36) ;
+
+CREATE UNIQUE INDEX uq_todos_series_id_due_at ON public.todos ( series_id, due_at );
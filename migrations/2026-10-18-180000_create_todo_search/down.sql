@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS set_todo_search ON public.todos;
+DROP FUNCTION IF EXISTS todo_search_update();
+DROP TABLE public.todo_search;
@@ -0,0 +1,27 @@
+-- Search vectors are kept in a separate table maintained by the trigger,
+-- so the todos table stays free of types that diesel doesn't know about
+CREATE TABLE public.todo_search
+(
+  todo_id varchar(36) NOT NULL ,
+  search_vector tsvector NOT NULL ,
+  CONSTRAINT pk_todo_search_todo_id PRIMARY KEY ( todo_id ) ,
+  CONSTRAINT fk_todo_search_todos FOREIGN KEY ( todo_id ) REFERENCES public.todos( id ) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_todo_search_search_vector ON public.todo_search USING GIN ( search_vector );
+
+CREATE OR REPLACE FUNCTION todo_search_update
+() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO public.todo_search ( todo_id, search_vector )
+    VALUES ( NEW.id, to_tsvector('english', NEW.content) )
+    ON CONFLICT ( todo_id ) DO UPDATE SET search_vector = EXCLUDED.search_vector;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER set_todo_search AFTER INSERT OR UPDATE OF content ON public.todos
+  FOR EACH ROW EXECUTE PROCEDURE todo_search_update();
+
+INSERT INTO public.todo_search ( todo_id, search_vector )
+SELECT id, to_tsvector('english', content) FROM public.todos;
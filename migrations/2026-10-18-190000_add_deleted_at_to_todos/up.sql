@@ -0,0 +1,3 @@
+ALTER TABLE public.todos ADD COLUMN deleted_at timestamptz ;
+
+CREATE INDEX idx_todos_deleted_at ON public.todos ( deleted_at );
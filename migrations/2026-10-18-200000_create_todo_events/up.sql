@@ -0,0 +1,19 @@
+CREATE TABLE public.todo_events
+(
+  id varchar(36) DEFAULT uuid_generate_v4() NOT NULL ,
+  todo_id varchar(36) NOT NULL ,
+  actor_id varchar(36) ,
+  kind varchar(16) NOT NULL ,
+  old_value jsonb ,
+  new_value jsonb ,
+  created_at timestamptz DEFAULT now() NOT NULL ,
+  CONSTRAINT pk_todo_events_id PRIMARY KEY ( id ) ,
+  CONSTRAINT fk_todo_events_todos FOREIGN KEY ( todo_id ) REFERENCES public.todos( id ) ON DELETE CASCADE ,
+  CONSTRAINT fk_todo_events_users FOREIGN KEY ( actor_id ) REFERENCES public.users( id ) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_todo_events_todo_id ON public.todo_events ( todo_id, created_at );
+
+INSERT INTO public.todo_events ( todo_id, actor_id, kind, new_value, created_at )
+SELECT id, user_id, 'create', jsonb_build_object('content', content), created_at
+FROM public.todos;
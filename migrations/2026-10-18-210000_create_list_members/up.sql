@@ -0,0 +1,12 @@
+CREATE TABLE public.list_members
+(
+  list_id varchar(36) NOT NULL ,
+  user_id varchar(36) NOT NULL ,
+  role varchar(16) NOT NULL ,
+  created_at timestamptz DEFAULT now() NOT NULL ,
+  CONSTRAINT pk_list_members PRIMARY KEY ( list_id, user_id ) ,
+  CONSTRAINT fk_list_members_lists FOREIGN KEY ( list_id ) REFERENCES public.lists( id ) ON DELETE CASCADE ,
+  CONSTRAINT fk_list_members_users FOREIGN KEY ( user_id ) REFERENCES public.users( id ) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_list_members_user_id ON public.list_members ( user_id );
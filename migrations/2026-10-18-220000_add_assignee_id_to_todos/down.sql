@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_todos_assignee_id;
+
+ALTER TABLE public.todos DROP CONSTRAINT IF EXISTS fk_todos_assignees;
+ALTER TABLE public.todos DROP COLUMN assignee_id;
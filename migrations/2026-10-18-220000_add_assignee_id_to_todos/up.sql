@@ -0,0 +1,4 @@
+ALTER TABLE public.todos ADD COLUMN assignee_id varchar(36) ;
+ALTER TABLE public.todos ADD CONSTRAINT fk_todos_assignees FOREIGN KEY ( assignee_id ) REFERENCES public.users( id ) ON DELETE SET NULL;
+
+CREATE INDEX idx_todos_assignee_id ON public.todos ( assignee_id );
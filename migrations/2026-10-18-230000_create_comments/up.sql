@@ -0,0 +1,16 @@
+CREATE TABLE public.comments
+(
+  id varchar(36) DEFAULT uuid_generate_v4() NOT NULL ,
+  todo_id varchar(36) NOT NULL ,
+  user_id varchar(36) NOT NULL ,
+  content text NOT NULL ,
+  created_at timestamptz DEFAULT now() NOT NULL ,
+  updated_at timestamptz DEFAULT now() NOT NULL ,
+  CONSTRAINT pk_comments_id PRIMARY KEY ( id ) ,
+  CONSTRAINT fk_comments_todos FOREIGN KEY ( todo_id ) REFERENCES public.todos( id ) ON DELETE CASCADE ,
+  CONSTRAINT fk_comments_users FOREIGN KEY ( user_id ) REFERENCES public.users( id ) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_comments_todo_id ON public.comments ( todo_id, created_at );
+
+SELECT diesel_manage_updated_at('public.comments');
@@ -0,0 +1,16 @@
+CREATE TABLE public.attachments
+(
+  id varchar(36) DEFAULT uuid_generate_v4() NOT NULL ,
+  todo_id varchar(36) NOT NULL ,
+  user_id varchar(36) NOT NULL ,
+  filename varchar(255) NOT NULL ,
+  content_type varchar(255) NOT NULL ,
+  size bigint NOT NULL ,
+  storage_key varchar(255) NOT NULL ,
+  created_at timestamptz DEFAULT now() NOT NULL ,
+  CONSTRAINT pk_attachments_id PRIMARY KEY ( id ) ,
+  CONSTRAINT fk_attachments_todos FOREIGN KEY ( todo_id ) REFERENCES public.todos( id ) ON DELETE CASCADE ,
+  CONSTRAINT fk_attachments_users FOREIGN KEY ( user_id ) REFERENCES public.users( id ) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attachments_todo_id ON public.attachments ( todo_id, created_at );
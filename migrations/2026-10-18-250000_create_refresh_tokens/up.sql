@@ -0,0 +1,16 @@
+CREATE TABLE public.refresh_tokens
+(
+  id varchar(36) DEFAULT uuid_generate_v4() NOT NULL ,
+  user_id varchar(36) NOT NULL ,
+  token_hash varchar(64) NOT NULL ,
+  jti varchar(36) NOT NULL ,
+  expires_at timestamptz NOT NULL ,
+  revoked_at timestamptz NULL ,
+  created_at timestamptz DEFAULT now() NOT NULL ,
+  CONSTRAINT pk_refresh_tokens_id PRIMARY KEY ( id ) ,
+  CONSTRAINT fk_refresh_tokens_users FOREIGN KEY ( user_id ) REFERENCES public.users( id ) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_refresh_tokens_token_hash ON public.refresh_tokens ( token_hash );
+CREATE UNIQUE INDEX idx_refresh_tokens_jti ON public.refresh_tokens ( jti );
+CREATE INDEX idx_refresh_tokens_user_id ON public.refresh_tokens ( user_id );
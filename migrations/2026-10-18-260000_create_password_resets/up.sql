@@ -0,0 +1,14 @@
+CREATE TABLE public.password_resets
+(
+  id varchar(36) DEFAULT uuid_generate_v4() NOT NULL ,
+  user_id varchar(36) NOT NULL ,
+  token_hash varchar(64) NOT NULL ,
+  expires_at timestamptz NOT NULL ,
+  used_at timestamptz NULL ,
+  created_at timestamptz DEFAULT now() NOT NULL ,
+  CONSTRAINT pk_password_resets_id PRIMARY KEY ( id ) ,
+  CONSTRAINT fk_password_resets_users FOREIGN KEY ( user_id ) REFERENCES public.users( id ) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_password_resets_token_hash ON public.password_resets ( token_hash );
+CREATE INDEX idx_password_resets_user_id ON public.password_resets ( user_id );
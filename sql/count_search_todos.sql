@@ -0,0 +1,11 @@
+select count(*) as count
+from public.todos t
+inner join public.todo_search s on s.todo_id = t.id
+where (
+  t.user_id = $1
+  or t.assignee_id = $1
+  or t.list_id in (select id from public.lists where user_id = $1)
+  or t.list_id in (select list_id from public.list_members where user_id = $1)
+)
+and t.deleted_at is null
+and s.search_vector @@ websearch_to_tsquery('english', $2)
@@ -0,0 +1,16 @@
+select t.*,
+  ts_rank(s.search_vector, query) as rank,
+  ts_headline('english', t.content, query, 'StartSel=<mark>, StopSel=</mark>') as snippet
+from public.todos t
+inner join public.todo_search s on s.todo_id = t.id,
+  websearch_to_tsquery('english', $2) query
+where (
+  t.user_id = $1
+  or t.assignee_id = $1
+  or t.list_id in (select id from public.lists where user_id = $1)
+  or t.list_id in (select list_id from public.list_members where user_id = $1)
+)
+and t.deleted_at is null
+and s.search_vector @@ query
+order by rank desc, t.id asc
+limit $3 offset $4
@@ -1,16 +1,40 @@
 use actix_cors::Cors;
 use actix_web::{http, middleware as actix_middleware, web, App, HttpServer};
 use env_logger::Env;
+use std::sync::Arc;
 
 pub async fn setup_web_server() -> std::io::Result<()> {
   env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
-  HttpServer::new(|| {
+
+  // Refuse to start without the usable keys for signing the tokens
+  let jwt = match crate::services::jwt::JwtConfig::from_env() {
+    Ok(jwt) => Arc::new(jwt),
+    Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, e)),
+  };
+  // Shared by all the workers so the users are looked up once per TTL
+  let users = Arc::new(crate::services::user_cache::UserCache::from_env());
+  let max_per_page = crate::state::app::max_per_page_from_env();
+
+  HttpServer::new(move || {
     App::new()
       // Init application state
-      .data(crate::state::app::initialize())
+      .data(crate::state::app::initialize(jwt.clone(), users.clone(), max_per_page))
       // Init setup of application request validators
       .app_data(crate::validation::new_todo_request::app_data())
       .app_data(crate::validation::new_user_request::app_data())
+      .app_data(crate::validation::update_todo_request::app_data())
+      .app_data(crate::validation::move_todo_request::app_data())
+      .app_data(crate::validation::list_request::app_data())
+      .app_data(crate::validation::member_request::app_data())
+      .app_data(crate::validation::tag_request::app_data())
+      .app_data(crate::validation::assign_todo_request::app_data())
+      .app_data(crate::validation::attach_tag_request::app_data())
+      .app_data(crate::validation::bulk_todo_request::app_data())
+      .app_data(crate::validation::comment_request::app_data())
+      .app_data(crate::validation::refresh_token_request::app_data())
+      .app_data(crate::validation::change_password_request::app_data())
+      .app_data(crate::validation::forgot_password_request::app_data())
+      .app_data(crate::validation::reset_password_request::app_data())
       // Logging setup
       .wrap(actix_middleware::Logger::default())
       .wrap(actix_middleware::Logger::new(
@@ -44,6 +68,26 @@ fn setup_routes(cfg: &mut web::ServiceConfig) {
   );
   // POST /login
   cfg.service(web::resource("/login").route(web::post().to(crate::routes::auth::login::handle)));
+  // POST /token/refresh
+  cfg.service(
+    web::resource("/token/refresh").route(web::post().to(crate::routes::auth::refresh::handle)),
+  );
+  // POST /password/forgot
+  cfg.service(
+    web::resource("/password/forgot")
+      .route(web::post().to(crate::routes::auth::forgot_password::handle)),
+  );
+  // POST /password/reset
+  cfg.service(
+    web::resource("/password/reset")
+      .route(web::post().to(crate::routes::auth::reset_password::handle)),
+  );
+  // POST /logout
+  cfg.service(
+    web::resource("/logout")
+      .route(web::post().to(crate::routes::auth::logout::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
   // GET /todos
   // POST /todos
   cfg.service(
@@ -52,6 +96,28 @@ fn setup_routes(cfg: &mut web::ServiceConfig) {
       .route(web::get().to(crate::routes::todos::index::handle))
       .wrap(crate::middleware::auth::LoggedGuard),
   );
+  // POST /todos/bulk
+  cfg.service(
+    web::resource("/todos/bulk")
+      .route(web::post().to(crate::routes::todos::bulk::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /todos/search
+  cfg.service(
+    web::resource("/todos/search")
+      .route(web::get().to(crate::routes::todos::search::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /todos/{todo_id}
+  // PUT /todos/{todo_id}
+  // DELETE /todos/{todo_id}
+  cfg.service(
+    web::resource("/todos/{todo_id}")
+      .route(web::get().to(crate::routes::todos::show::handle))
+      .route(web::put().to(crate::routes::todos::update::handle))
+      .route(web::delete().to(crate::routes::todos::delete::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
   // POST /todos/{todo_id}/check
   cfg.service(
     web::resource("/todos/{todo_id}/check")
@@ -64,10 +130,150 @@ fn setup_routes(cfg: &mut web::ServiceConfig) {
       .route(web::post().to(crate::routes::todos::uncheck::handle))
       .wrap(crate::middleware::auth::LoggedGuard),
   );
+  // POST /todos/{todo_id}/move
+  cfg.service(
+    web::resource("/todos/{todo_id}/move")
+      .route(web::post().to(crate::routes::todos::reorder::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /todos/{todo_id}/subtasks
+  // POST /todos/{todo_id}/subtasks
+  cfg.service(
+    web::resource("/todos/{todo_id}/subtasks")
+      .route(web::post().to(crate::routes::todos::subtasks::store::handle))
+      .route(web::get().to(crate::routes::todos::subtasks::index::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // POST /todos/{todo_id}/assign
+  cfg.service(
+    web::resource("/todos/{todo_id}/assign")
+      .route(web::post().to(crate::routes::todos::assign::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // POST /todos/{todo_id}/tags
+  cfg.service(
+    web::resource("/todos/{todo_id}/tags")
+      .route(web::post().to(crate::routes::todos::attach_tag::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // DELETE /todos/{todo_id}/tags/{tag_id}
+  cfg.service(
+    web::resource("/todos/{todo_id}/tags/{tag_id}")
+      .route(web::delete().to(crate::routes::todos::detach_tag::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /lists
+  // POST /lists
+  cfg.service(
+    web::resource("/lists")
+      .route(web::post().to(crate::routes::lists::store::handle))
+      .route(web::get().to(crate::routes::lists::index::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /lists/{list_id}
+  // PUT /lists/{list_id}
+  // DELETE /lists/{list_id}
+  cfg.service(
+    web::resource("/lists/{list_id}")
+      .route(web::get().to(crate::routes::lists::show::handle))
+      .route(web::put().to(crate::routes::lists::update::handle))
+      .route(web::delete().to(crate::routes::lists::delete::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /lists/{list_id}/todos
+  cfg.service(
+    web::resource("/lists/{list_id}/todos")
+      .route(web::get().to(crate::routes::lists::todos::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /lists/{list_id}/members
+  // POST /lists/{list_id}/members
+  cfg.service(
+    web::resource("/lists/{list_id}/members")
+      .route(web::get().to(crate::routes::lists::members::index::handle))
+      .route(web::post().to(crate::routes::lists::members::store::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // DELETE /lists/{list_id}/members/{user_id}
+  cfg.service(
+    web::resource("/lists/{list_id}/members/{user_id}")
+      .route(web::delete().to(crate::routes::lists::members::delete::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /todos/{todo_id}/attachments
+  // POST /todos/{todo_id}/attachments
+  cfg.service(
+    web::resource("/todos/{todo_id}/attachments")
+      .route(web::get().to(crate::routes::todos::attachments::index::handle))
+      .route(web::post().to(crate::routes::todos::attachments::store::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /todos/{todo_id}/attachments/{attachment_id}
+  // DELETE /todos/{todo_id}/attachments/{attachment_id}
+  cfg.service(
+    web::resource("/todos/{todo_id}/attachments/{attachment_id}")
+      .route(web::get().to(crate::routes::todos::attachments::show::handle))
+      .route(web::delete().to(crate::routes::todos::attachments::delete::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /todos/{todo_id}/comments
+  // POST /todos/{todo_id}/comments
+  cfg.service(
+    web::resource("/todos/{todo_id}/comments")
+      .route(web::get().to(crate::routes::todos::comments::index::handle))
+      .route(web::post().to(crate::routes::todos::comments::store::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // PUT /todos/{todo_id}/comments/{comment_id}
+  // DELETE /todos/{todo_id}/comments/{comment_id}
+  cfg.service(
+    web::resource("/todos/{todo_id}/comments/{comment_id}")
+      .route(web::put().to(crate::routes::todos::comments::update::handle))
+      .route(web::delete().to(crate::routes::todos::comments::delete::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /todos/{todo_id}/history
+  cfg.service(
+    web::resource("/todos/{todo_id}/history")
+      .route(web::get().to(crate::routes::todos::history::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // DELETE /todos/{todo_id}/recurrence
+  cfg.service(
+    web::resource("/todos/{todo_id}/recurrence")
+      .route(web::delete().to(crate::routes::todos::stop_recurrence::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // POST /todos/{todo_id}/restore
+  cfg.service(
+    web::resource("/todos/{todo_id}/restore")
+      .route(web::post().to(crate::routes::todos::restore::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /trash
+  cfg.service(
+    web::resource("/trash")
+      .route(web::get().to(crate::routes::trash::index::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
+  // GET /tags
+  // POST /tags
+  cfg.service(
+    web::resource("/tags")
+      .route(web::post().to(crate::routes::tags::store::handle))
+      .route(web::get().to(crate::routes::tags::index::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
   // POST /self
   cfg.service(
     web::resource("/self")
       .route(web::post().to(crate::routes::users::index::handle))
       .wrap(crate::middleware::auth::LoggedGuard),
   );
+  // POST /self/password
+  cfg.service(
+    web::resource("/self/password")
+      .route(web::post().to(crate::routes::users::password::handle))
+      .wrap(crate::middleware::auth::LoggedGuard),
+  );
 }
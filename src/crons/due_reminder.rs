@@ -0,0 +1,59 @@
+use crate::models::todo::Todo;
+use crate::services::notifier::{Notifier, Reminder};
+use chrono::{Duration, Utc};
+use crony::{Job, Schedule};
+use std::str::FromStr;
+
+/// Job that reminds the owners about their todos which are going to be
+/// due within the configured window. Every todo is marked as reminded in
+/// the database before the notification goes out, so it is reminded only once.
+pub struct DueReminderJob {
+  pub notifier: Box<dyn Notifier>,
+  pub window: Duration,
+}
+
+impl Job for DueReminderJob {
+  fn schedule(&self) -> Schedule {
+    Schedule::from_str("0 * * * * *").unwrap()
+  }
+
+  fn handle(&self) {
+    let connection = crate::state::pool::get_single_connection();
+    let until = Utc::now() + self.window;
+
+    let due = match Todo::due_for_reminder(&connection, until) {
+      Ok(due) => due,
+      Err(e) => {
+        println!("Due reminder: Could not load due todos: {:?}", e);
+        return;
+      }
+    };
+
+    for (todo, user) in due {
+      match todo.mark_reminded(&connection) {
+        Ok(true) => (),
+        Ok(false) => continue,
+        Err(e) => {
+          println!("Due reminder: Could not mark todo {}: {:?}", todo.id, e);
+          continue;
+        }
+      }
+
+      let reminder = Reminder {
+        user_id: user.id,
+        email: user.email,
+        todo_id: String::from(&todo.id),
+        content: String::from(&todo.content),
+        due_at: todo.due_at.unwrap_or_else(Utc::now),
+      };
+
+      if let Err(e) = self.notifier.notify(&reminder) {
+        println!("Due reminder: Could not notify about todo {}: {:?}", todo.id, e);
+        // Let the next run retry the reminder
+        if let Err(e) = todo.unmark_reminded(&connection) {
+          println!("Due reminder: Could not unmark todo {}: {:?}", todo.id, e);
+        }
+      }
+    }
+  }
+}
@@ -1,18 +1,10 @@
-use crony::{Job, Runner, Schedule};
+mod due_reminder;
+mod purge_trash;
+mod recurrence;
+
+use crony::Runner;
 use dotenv::dotenv;
 use std::env;
-use std::str::FromStr;
-
-struct ExampleJob;
-impl Job for ExampleJob {
-  fn schedule(&self) -> Schedule {
-    Schedule::from_str("0 * * * * *").unwrap()
-  }
-
-  fn handle(&self) {
-    println!("Hello, I am cron job running at: {}", self.now());
-  }
-}
 
 /// Cron runner
 pub fn run_crons() {
@@ -20,6 +12,48 @@ pub fn run_crons() {
   let activate_cron = env::var("CRON_ACTIVE").unwrap_or("false".into());
   if activate_cron == String::from("true") {
     println!("Starting cron runner.");
-    Runner::new().add(Box::new(ExampleJob)).run();
+    Runner::new()
+      .add(Box::new(due_reminder_job()))
+      .add(Box::new(recurrence_job()))
+      .add(Box::new(purge_trash_job()))
+      .run();
+  }
+}
+
+/// Setup the due reminder job with the window configured in minutes
+fn due_reminder_job() -> due_reminder::DueReminderJob {
+  let window: i64 = env::var("REMINDER_WINDOW_IN_MINUTES")
+    .ok()
+    .and_then(|minutes| minutes.parse().ok())
+    .unwrap_or(60);
+
+  due_reminder::DueReminderJob {
+    notifier: crate::services::notifier::from_env(),
+    window: chrono::Duration::minutes(window),
+  }
+}
+
+/// Setup the recurrence job with the horizon configured in days
+fn recurrence_job() -> recurrence::RecurrenceJob {
+  let horizon: i64 = env::var("RECURRENCE_HORIZON_IN_DAYS")
+    .ok()
+    .and_then(|days| days.parse().ok())
+    .unwrap_or(7);
+
+  recurrence::RecurrenceJob {
+    horizon: chrono::Duration::days(horizon),
+  }
+}
+
+/// Setup the trash purging job with the retention configured in days
+fn purge_trash_job() -> purge_trash::PurgeTrashJob {
+  let retention: i64 = env::var("TRASH_RETENTION_IN_DAYS")
+    .ok()
+    .and_then(|days| days.parse().ok())
+    .unwrap_or(30);
+
+  purge_trash::PurgeTrashJob {
+    retention: chrono::Duration::days(retention),
+    storage: crate::services::storage::from_env(),
   }
 }
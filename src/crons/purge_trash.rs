@@ -0,0 +1,40 @@
+use crate::models::todo::Todo;
+use crate::services::storage::Storage;
+use chrono::{Duration, Utc};
+use crony::{Job, Schedule};
+use std::str::FromStr;
+
+/// Job that removes the todos for good once they have been in the trash
+/// for longer than the retention period. Files attached to the purged todos
+/// are removed from the storage as well.
+pub struct PurgeTrashJob {
+  pub retention: Duration,
+  pub storage: Box<dyn Storage>,
+}
+
+impl Job for PurgeTrashJob {
+  fn schedule(&self) -> Schedule {
+    Schedule::from_str("0 30 * * * *").unwrap()
+  }
+
+  fn handle(&self) {
+    let connection = crate::state::pool::get_single_connection();
+    let before = Utc::now() - self.retention;
+
+    let storage_keys = match Todo::purge_trashed(&connection, before) {
+      Ok((purged, storage_keys)) => {
+        if purged > 0 {
+          println!("Trash: Purged {} todos", purged);
+        }
+        storage_keys
+      }
+      Err(e) => return println!("Trash: Could not purge trashed todos: {:?}", e),
+    };
+
+    for key in storage_keys {
+      if let Err(e) = self.storage.delete(&key) {
+        println!("Trash: Could not delete attachment {}: {:?}", key, e);
+      }
+    }
+  }
+}
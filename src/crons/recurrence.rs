@@ -0,0 +1,43 @@
+use crate::models::todo::Todo;
+use chrono::{Duration, Utc};
+use crony::{Job, Schedule};
+use std::str::FromStr;
+
+/// Job that creates the occurrences of recurring todos ahead of time, so
+/// they are in place before they are due even if nobody checked the
+/// previous occurrence.
+pub struct RecurrenceJob {
+  pub horizon: Duration,
+}
+
+impl Job for RecurrenceJob {
+  fn schedule(&self) -> Schedule {
+    Schedule::from_str("0 0 * * * *").unwrap()
+  }
+
+  fn handle(&self) {
+    let connection = crate::state::pool::get_single_connection();
+    let until = Utc::now() + self.horizon;
+
+    let latest = match Todo::latest_occurrences(&connection) {
+      Ok(latest) => latest,
+      Err(e) => {
+        println!("Recurrence: Could not load recurring todos: {:?}", e);
+        return;
+      }
+    };
+
+    for mut todo in latest {
+      while todo.due_at.is_some_and(|due_at| due_at < until) {
+        todo = match todo.schedule_next(&connection) {
+          Ok(Some(next)) => next,
+          Ok(None) => break,
+          Err(e) => {
+            println!("Recurrence: Could not create occurrence of {}: {:?}", todo.id, e);
+            break;
+          }
+        };
+      }
+    }
+  }
+}
@@ -15,9 +15,9 @@ pub mod schema;
 pub mod services;
 pub mod state;
 pub mod validation;
-pub mod virtual_schema;
 
 pub const DEFAULT_PER_PAGE: u32 = 15;
+pub const MAX_PER_PAGE: u32 = 100;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
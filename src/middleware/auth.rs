@@ -3,12 +3,17 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use crate::models::auth::AuthenticableUser;
+use crate::models::refresh_token::RefreshToken;
 use crate::state::app::AppState;
 use actix_service::{Service, Transform};
-use actix_web::{dev::ServiceRequest, dev::ServiceResponse, Error, HttpMessage};
+use actix_web::{dev::ServiceRequest, dev::ServiceResponse, web, Error, HttpMessage};
 use futures::future::{ok, Ready};
 pub struct LoggedGuard;
 
+/// Id of the JWT that the request was authenticated with, it is put into
+/// the request extensions next to the user when bearer auth is used.
+pub struct TokenId(pub String);
+
 impl<S> Transform<S> for LoggedGuard
 where
   S: Service<Request = ServiceRequest, Response = ServiceResponse, Error = Error>,
@@ -46,8 +51,11 @@ where
 
   fn call(&mut self, req: ServiceRequest) -> Self::Future {
     match is_logged(&req) {
-      Ok(auth) => {
+      Ok((auth, token_id)) => {
         req.extensions_mut().insert(auth);
+        if let Some(token_id) = token_id {
+          req.extensions_mut().insert(token_id);
+        }
         let fut = self.service.call(req);
         Box::pin(async move {
           let res = fut.await?;
@@ -68,7 +76,9 @@ where
 }
 
 /// Check if the user making the request is logged in
-fn is_logged(req: &ServiceRequest) -> Result<crate::models::user::User, String> {
+fn is_logged(
+  req: &ServiceRequest,
+) -> Result<(crate::models::user::User, Option<TokenId>), String> {
   let header = match &req.headers().get("Authorization") {
     Some(head) => match head.to_str().ok() {
       Some(val) => val.to_string(),
@@ -82,30 +92,65 @@ fn is_logged(req: &ServiceRequest) -> Result<crate::models::user::User, String>
   let auth_type = split.next();
 
   if Some("Bearer") == auth_type {
-    bearer_auth(match split.next() {
-      Some(v) => v,
-      None => "",
-    })
+    let (user, jti) = bearer_auth(
+      match split.next() {
+        Some(v) => v,
+        None => "",
+      },
+      req,
+    )?;
+
+    Ok((user, Some(TokenId(jti))))
   } else if Some("Basic") == auth_type {
-    basic_auth(
+    let user = basic_auth(
       match split.next() {
         Some(v) => v,
         None => "",
       },
       req,
-    )
+    )?;
+
+    Ok((user, None))
   } else {
     Err(String::from("Not valid authentication method"))
   }
 }
 
-/// Handle JWT authentication token
-fn bearer_auth(data: &str) -> Result<crate::models::user::User, String> {
-  match crate::services::jwt::verify(String::from(data)) {
-    Ok(user) => Ok(user),
+/// Handle JWT authentication token, tokens that were revoked or belong to
+/// the user that no longer exists are refused. The revocation is checked on
+/// every request so logging out takes effect right away, only the user is
+/// looked up through the cache.
+fn bearer_auth(
+  data: &str,
+  req: &ServiceRequest,
+) -> Result<(crate::models::user::User, String), String> {
+  let state = state(req);
+  let claims = match state.jwt().verify(data) {
+    Ok(claims) => claims,
     Err(e) => {
       println!("Got error from jwt: {:?}", e);
-      Err(String::from("Something wrong with the signature"))
+      return Err(String::from("Something wrong with the signature"));
+    }
+  };
+
+  let connection = state.get_connection();
+
+  match RefreshToken::is_active(&connection, &claims.jti) {
+    Ok(true) => {}
+    Ok(false) => return Err(String::from("Token was revoked")),
+    Err(e) => {
+      println!("Could not check token revocation: {:?}", e);
+      return Err(String::from("Could not verify the token"));
+    }
+  }
+
+  match state.users().find(&connection, &claims.sub) {
+    Ok(Some(user)) if user.email == claims.email => Ok((user, claims.jti)),
+    Ok(Some(_)) => Err(String::from("Token was issued for another email")),
+    Ok(None) => Err(String::from("User no longer exists")),
+    Err(e) => {
+      println!("Could not look up the user: {:?}", e);
+      Err(String::from("Could not verify the token"))
     }
   }
 }
@@ -134,11 +179,8 @@ fn basic_auth(data: &str, req: &ServiceRequest) -> Result<crate::models::user::U
     None => "",
   };
 
-  // We will try to get app state here and unwrap it, in case the app data does not exist
-  // we want to panic, there is no recovery from it missing.
-  let state = req.app_data::<AppState>().unwrap();
-  match AuthenticableUser::authenticate(&state.get_connection(), &email, &password) {
-    Ok((user, _)) => Ok(user),
+  match AuthenticableUser::authenticate(&state(req).get_connection(), &email, &password) {
+    Ok(user) => Ok(user),
     Err(e) => {
       println!("Basic auth error: {:?}", e);
 
@@ -146,3 +188,9 @@ fn basic_auth(data: &str, req: &ServiceRequest) -> Result<crate::models::user::U
     }
   }
 }
+
+/// We will try to get app state here and unwrap it, in case the app data does not exist
+/// we want to panic, there is no recovery from it missing.
+fn state(req: &ServiceRequest) -> &AppState {
+  req.app_data::<web::Data<AppState>>().unwrap()
+}
@@ -0,0 +1,84 @@
+use super::super::schema::attachments;
+use crate::diesel::ExpressionMethods;
+use crate::diesel::QueryDsl;
+use crate::diesel::RunQueryDsl;
+use chrono::{DateTime, Utc};
+use diesel::result;
+
+/// File attached to the todo, the content itself is kept in the storage
+/// under the storage key
+#[derive(Queryable, PartialEq, Debug, serde::Serialize)]
+pub struct Attachment {
+  pub id: String,
+  pub todo_id: String,
+  pub user_id: String,
+  pub filename: String,
+  pub content_type: String,
+  pub size: i64,
+  #[serde(skip_serializing)]
+  pub storage_key: String,
+  pub created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+  /// Get all attachments of the todo, the oldest come first
+  pub fn for_todo(
+    connection: &crate::diesel::PgConnection,
+    todo_id: &str,
+  ) -> Result<Vec<Attachment>, result::Error> {
+    attachments::table
+      .filter(attachments::todo_id.eq(todo_id))
+      .order((attachments::created_at.asc(), attachments::id.asc()))
+      .load::<Attachment>(connection)
+  }
+
+  /// Get single attachment out of the database
+  pub fn show(connection: &crate::diesel::PgConnection, id: &str) -> Result<Self, result::Error> {
+    attachments::table.find(id).first::<Self>(connection)
+  }
+
+  /// Get the storage keys of the attachments on given todos
+  pub fn storage_keys(
+    connection: &crate::diesel::PgConnection,
+    todo_ids: &[String],
+  ) -> Result<Vec<String>, result::Error> {
+    attachments::table
+      .filter(attachments::todo_id.eq_any(todo_ids))
+      .select(attachments::storage_key)
+      .load::<String>(connection)
+  }
+
+  /// Remove the attachment from the database
+  pub fn delete(&self, connection: &crate::diesel::PgConnection) -> Result<bool, result::Error> {
+    let deleted = diesel::delete(attachments::table.find(&self.id)).execute(connection)?;
+
+    if deleted == 0 {
+      Err(result::Error::NotFound)
+    } else {
+      Ok(true)
+    }
+  }
+}
+
+#[derive(Insertable)]
+#[table_name = "attachments"]
+pub struct NewAttachment {
+  pub todo_id: String,
+  pub user_id: String,
+  pub filename: String,
+  pub content_type: String,
+  pub size: i64,
+  pub storage_key: String,
+}
+
+impl NewAttachment {
+  /// Insert the attachment once its content is already in the storage
+  pub fn insert(
+    &self,
+    connection: &crate::diesel::PgConnection,
+  ) -> Result<Attachment, result::Error> {
+    diesel::insert_into(attachments::table)
+      .values(self)
+      .get_result::<Attachment>(connection)
+  }
+}
@@ -17,7 +17,7 @@ impl AuthenticableUser {
     connection: &crate::diesel::PgConnection,
     email: &'b str,
     password: &'b str,
-  ) -> Result<(User, String), AuthenticationError> {
+  ) -> Result<User, AuthenticationError> {
     let user = match users::table
       .filter(users::email.eq(&email))
       .load::<User>(connection)
@@ -40,9 +40,7 @@ impl AuthenticableUser {
 
     AuthenticableUser::verify(password.into(), &user)?;
 
-    let token = user.generate_jwt();
-
-    Ok((user, token))
+    Ok(user)
   }
 
   /// Verify the bcrypt password
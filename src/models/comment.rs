@@ -0,0 +1,132 @@
+use super::super::schema::comments;
+use super::todo_event::{EventKind, NewTodoEvent};
+use super::Paginated;
+use crate::diesel::ExpressionMethods;
+use crate::diesel::QueryDsl;
+use crate::diesel::RunQueryDsl;
+use chrono::{DateTime, Utc};
+use diesel::result;
+use diesel::Connection;
+use serde_json::json;
+
+/// Comment of the user on the todo
+#[derive(Queryable, PartialEq, Debug, serde::Serialize)]
+pub struct Comment {
+  pub id: String,
+  pub todo_id: String,
+  pub user_id: String,
+  pub content: String,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl Comment {
+  /// Get paginated comments on the todo, the oldest come first
+  pub fn paginated(
+    connection: &crate::diesel::PgConnection,
+    page: u32,
+    per_page: u32,
+    todo_id: &str,
+  ) -> Result<Paginated<Comment>, result::Error> {
+    let mut data: Vec<Comment> = vec![];
+
+    let total: i64 = comments::table
+      .filter(comments::todo_id.eq(todo_id))
+      .count()
+      .get_result(connection)?;
+
+    if total > 0 {
+      let skip = (page as i64 - 1) * per_page as i64;
+
+      data = comments::table
+        .filter(comments::todo_id.eq(todo_id))
+        .order((comments::created_at.asc(), comments::id.asc()))
+        .offset(skip)
+        .limit(per_page as i64)
+        .load::<Comment>(connection)?;
+    }
+
+    Ok(Paginated::new(page, per_page, total as u32, data))
+  }
+
+  /// Get single comment out of the database
+  pub fn show(connection: &crate::diesel::PgConnection, id: &str) -> Result<Self, result::Error> {
+    comments::table.find(id).first::<Self>(connection)
+  }
+
+  /// Replace the content of the comment, the edit is recorded in the
+  /// history of the todo without the content itself
+  pub fn update_content(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    content: &str,
+  ) -> Result<Comment, result::Error> {
+    connection.transaction(|| {
+      let updated = diesel::update(comments::table.find(&self.id))
+        .set(comments::content.eq(content))
+        .get_result::<Comment>(connection)?;
+
+      NewTodoEvent::new(&self.todo_id, Some(&self.user_id), EventKind::CommentEdited)
+        .change(None, Some(json!({ "comment_id": updated.id })))
+        .record(connection)?;
+
+      Ok(updated)
+    })
+  }
+
+  /// Remove the comment from the database, the removal is recorded in the
+  /// history of the todo. History keeps only the id of the comment, so its
+  /// content is gone for good.
+  pub fn delete(&self, connection: &crate::diesel::PgConnection) -> Result<bool, result::Error> {
+    connection.transaction(|| {
+      let deleted = diesel::delete(comments::table.find(&self.id)).execute(connection)?;
+
+      if deleted == 0 {
+        return Err(result::Error::NotFound);
+      }
+
+      NewTodoEvent::new(&self.todo_id, Some(&self.user_id), EventKind::CommentDeleted)
+        .change(Some(json!({ "comment_id": self.id })), None)
+        .record(connection)?;
+
+      Ok(true)
+    })
+  }
+}
+
+#[derive(Insertable)]
+#[table_name = "comments"]
+pub struct NewComment {
+  pub todo_id: String,
+  pub user_id: String,
+  pub content: String,
+}
+
+impl NewComment {
+  /// Create new comment of the user on the todo, the comment is recorded
+  /// in the history of the todo as well
+  pub fn create<'a>(
+    connection: &crate::diesel::PgConnection,
+    todo_id: &'a str,
+    user_id: &'a str,
+    content: &'a str,
+  ) -> Result<Comment, result::Error> {
+    let values = Self {
+      todo_id: String::from(todo_id),
+      user_id: String::from(user_id),
+      content: String::from(content),
+    };
+
+    connection.transaction(|| {
+      let comment = diesel::insert_into(comments::table)
+        .values(&values)
+        .get_result::<Comment>(connection)?;
+
+      NewTodoEvent::new(todo_id, Some(user_id), EventKind::CommentAdded)
+        .change(None, Some(json!({ "comment_id": comment.id })))
+        .record(connection)?;
+
+      Ok(comment)
+    })
+  }
+}
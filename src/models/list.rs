@@ -0,0 +1,111 @@
+use super::super::schema::{list_members, lists};
+use super::Paginated;
+use crate::diesel::BoolExpressionMethods;
+use crate::diesel::ExpressionMethods;
+use crate::diesel::QueryDsl;
+use crate::diesel::RunQueryDsl;
+use chrono::{DateTime, Utc};
+use diesel::pg::Pg;
+use diesel::result;
+
+/// List that groups the todos of the user, e.g. "work" or "groceries"
+#[derive(Queryable, PartialEq, Debug, serde::Serialize)]
+pub struct List {
+  pub id: String,
+  pub user_id: String,
+  pub name: String,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl List {
+  /// Get paginated lists for user, including the lists shared with them
+  pub fn paginated(
+    connection: &crate::diesel::PgConnection,
+    page: u32,
+    per_page: u32,
+    user_id: &str,
+  ) -> Result<Paginated<List>, result::Error> {
+    let mut data: Vec<List> = vec![];
+
+    let total: i64 = Self::accessible(user_id).count().get_result(connection)?;
+
+    if total > 0 {
+      let skip = (page as i64 - 1) * per_page as i64;
+
+      data = Self::accessible(user_id)
+        .order((lists::name.asc(), lists::id.asc()))
+        .offset(skip)
+        .limit(per_page as i64)
+        .load::<List>(connection)?;
+    }
+
+    Ok(Paginated::new(page, per_page, total as u32, data))
+  }
+
+  /// Query for the lists that the user owns or that were shared with them
+  fn accessible(user_id: &str) -> lists::BoxedQuery<'_, Pg> {
+    let shared = list_members::table
+      .filter(list_members::user_id.eq(user_id))
+      .select(list_members::list_id);
+
+    lists::table
+      .filter(lists::user_id.eq(user_id).or(lists::id.eq_any(shared)))
+      .into_boxed()
+  }
+
+  /// Get single list out of the database
+  pub fn show(connection: &crate::diesel::PgConnection, id: &str) -> Result<Self, result::Error> {
+    lists::table.find(id).first::<Self>(connection)
+  }
+
+  /// Rename the list
+  pub fn update_name(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    name: &str,
+  ) -> Result<List, result::Error> {
+    let target = lists::table.filter(lists::id.eq(&self.id));
+
+    diesel::update(target)
+      .set(lists::name.eq(name))
+      .get_result::<List>(connection)
+  }
+
+  /// Remove the list from the database, its todos are kept without the list
+  pub fn delete(&self, connection: &crate::diesel::PgConnection) -> Result<bool, result::Error> {
+    let target = lists::table.filter(lists::id.eq(&self.id));
+    let deleted = diesel::delete(target).execute(connection)?;
+
+    if deleted == 0 {
+      Err(result::Error::NotFound)
+    } else {
+      Ok(true)
+    }
+  }
+}
+
+#[derive(Insertable)]
+#[table_name = "lists"]
+pub struct NewList {
+  pub user_id: String,
+  pub name: String,
+}
+
+impl NewList {
+  /// Create new list for the user
+  pub fn create<'a>(
+    connection: &crate::diesel::PgConnection,
+    user_id: &'a str,
+    name: &'a str,
+  ) -> Result<List, result::Error> {
+    let values = Self {
+      user_id: String::from(user_id),
+      name: String::from(name),
+    };
+
+    diesel::insert_into(lists::table)
+      .values(&values)
+      .get_result::<List>(connection)
+  }
+}
@@ -0,0 +1,162 @@
+use super::super::schema::{list_members, users};
+use super::user::User;
+use crate::diesel::ExpressionMethods;
+use crate::diesel::QueryDsl;
+use crate::diesel::RunQueryDsl;
+use chrono::{DateTime, Utc};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::upsert::excluded;
+use diesel::pg::Pg;
+use diesel::result;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use diesel::OptionalExtension;
+use std::io::Write;
+
+/// Role of the user on the shared list, later roles include everything
+/// that the earlier ones are allowed to do.
+#[derive(
+  AsExpression,
+  FromSqlRow,
+  Clone,
+  Copy,
+  PartialEq,
+  PartialOrd,
+  Debug,
+  serde::Serialize,
+  serde::Deserialize,
+)]
+#[sql_type = "Text"]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+  Viewer,
+  Editor,
+  Owner,
+}
+
+impl ToSql<Text, Pg> for Role {
+  fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+    let value = match self {
+      Role::Viewer => "viewer",
+      Role::Editor => "editor",
+      Role::Owner => "owner",
+    };
+
+    ToSql::<Text, Pg>::to_sql(value, out)
+  }
+}
+
+impl FromSql<Text, Pg> for Role {
+  fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+    match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+      "viewer" => Ok(Role::Viewer),
+      "editor" => Ok(Role::Editor),
+      "owner" => Ok(Role::Owner),
+      value => Err(format!("Unknown role: {}", value).into()),
+    }
+  }
+}
+
+/// User that the list was shared with
+#[derive(Queryable, PartialEq, Debug, serde::Serialize)]
+pub struct ListMember {
+  pub list_id: String,
+  pub user_id: String,
+  pub role: Role,
+  pub created_at: DateTime<Utc>,
+}
+
+/// Member of the list together with the email of the user
+#[derive(PartialEq, Debug, serde::Serialize)]
+pub struct Member {
+  pub user_id: String,
+  pub email: String,
+  pub role: Role,
+  pub created_at: DateTime<Utc>,
+}
+
+impl ListMember {
+  /// Get all the members of the list, the earliest invited come first
+  pub fn for_list(
+    connection: &crate::diesel::PgConnection,
+    list_id: &str,
+  ) -> Result<Vec<Member>, result::Error> {
+    let rows = list_members::table
+      .inner_join(users::table)
+      .filter(list_members::list_id.eq(list_id))
+      .order((list_members::created_at.asc(), list_members::user_id.asc()))
+      .load::<(ListMember, User)>(connection)?;
+
+    Ok(
+      rows
+        .into_iter()
+        .map(|(member, user)| Member {
+          user_id: member.user_id,
+          email: user.email,
+          role: member.role,
+          created_at: member.created_at,
+        })
+        .collect(),
+    )
+  }
+
+  /// Role of the user on the list, none when the list wasn't shared with them
+  pub fn role_of(
+    connection: &crate::diesel::PgConnection,
+    list_id: &str,
+    user_id: &str,
+  ) -> Result<Option<Role>, result::Error> {
+    list_members::table
+      .find((list_id, user_id))
+      .select(list_members::role)
+      .first::<Role>(connection)
+      .optional()
+  }
+
+  /// Stop sharing the list with the user
+  pub fn remove(
+    connection: &crate::diesel::PgConnection,
+    list_id: &str,
+    user_id: &str,
+  ) -> Result<bool, result::Error> {
+    let deleted = diesel::delete(list_members::table.find((list_id, user_id))).execute(connection)?;
+
+    if deleted == 0 {
+      Err(result::Error::NotFound)
+    } else {
+      Ok(true)
+    }
+  }
+}
+
+#[derive(Insertable)]
+#[table_name = "list_members"]
+pub struct NewListMember {
+  pub list_id: String,
+  pub user_id: String,
+  pub role: Role,
+}
+
+impl NewListMember {
+  /// Share the list with the user, the role is replaced when the list
+  /// was already shared with them.
+  pub fn save<'a>(
+    connection: &crate::diesel::PgConnection,
+    list_id: &'a str,
+    user_id: &'a str,
+    role: Role,
+  ) -> Result<ListMember, result::Error> {
+    let values = Self {
+      list_id: String::from(list_id),
+      user_id: String::from(user_id),
+      role,
+    };
+
+    diesel::insert_into(list_members::table)
+      .values(&values)
+      .on_conflict((list_members::list_id, list_members::user_id))
+      .do_update()
+      .set(list_members::role.eq(excluded(list_members::role)))
+      .get_result::<ListMember>(connection)
+  }
+}
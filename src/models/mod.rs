@@ -1,5 +1,15 @@
+pub mod attachment;
 pub mod auth;
+pub mod comment;
+pub mod list;
+pub mod list_member;
+pub mod password_reset;
+pub mod priority;
+pub mod recurrence;
+pub mod refresh_token;
+pub mod tag;
 pub mod todo;
+pub mod todo_event;
 pub mod user;
 
 #[derive(serde::Serialize)]
@@ -9,4 +19,43 @@ pub struct Paginated<T> {
   total: u32,
   last_page: u32,
   data: Vec<T>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  next_cursor: Option<String>,
+}
+
+impl<T> Paginated<T> {
+  /// Wrap single page of the data together with the page numbers
+  pub fn new(page: u32, per_page: u32, total: u32, data: Vec<T>) -> Self {
+    Paginated {
+      page,
+      per_page,
+      total,
+      last_page: last_page(total, per_page),
+      data,
+      next_cursor: None,
+    }
+  }
+
+  /// Attach the cursor that continues right after this page
+  pub fn with_next_cursor(mut self, next_cursor: Option<String>) -> Self {
+    self.next_cursor = next_cursor;
+    self
+  }
+}
+
+/// Number of the last page, there is always at least one page
+fn last_page(total: u32, per_page: u32) -> u32 {
+  total.div_ceil(per_page.max(1)).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::last_page;
+  #[test]
+  fn count_last_page() {
+    assert_eq!(last_page(0, 15), 1);
+    assert_eq!(last_page(15, 15), 1);
+    assert_eq!(last_page(16, 15), 2);
+    assert_eq!(last_page(120, 10), 12);
+  }
 }
@@ -0,0 +1,103 @@
+use super::super::schema::{password_resets, users};
+use super::refresh_token::RefreshToken;
+use super::user::User;
+use crate::diesel::ExpressionMethods;
+use crate::diesel::QueryDsl;
+use crate::diesel::RunQueryDsl;
+use crate::services::token::{generate, hash};
+use chrono::{DateTime, Duration, Utc};
+use diesel::result;
+use diesel::Connection;
+
+/// Request of the user to reset the forgotten password, only the hash of
+/// the token that was sent to the user is kept. Token can be used once.
+#[derive(Queryable, PartialEq, Debug)]
+pub struct PasswordReset {
+  pub id: String,
+  pub user_id: String,
+  pub token_hash: String,
+  pub expires_at: DateTime<Utc>,
+  pub used_at: Option<DateTime<Utc>>,
+  pub created_at: DateTime<Utc>,
+}
+
+impl PasswordReset {
+  /// Set the new password of the user with the reset token, all the existing
+  /// sessions of the user get revoked. None is returned when the token is
+  /// unknown, already used or expired.
+  pub fn reset(
+    connection: &crate::diesel::PgConnection,
+    token: &str,
+    password: &str,
+  ) -> Result<Option<User>, result::Error> {
+    connection.transaction(|| {
+      let reset = match password_resets::table
+        .filter(password_resets::token_hash.eq(hash(token)))
+        .filter(password_resets::used_at.is_null())
+        .filter(password_resets::expires_at.gt(Utc::now()))
+        .for_update()
+        .first::<PasswordReset>(connection)
+      {
+        Ok(reset) => reset,
+        Err(result::Error::NotFound) => return Ok(None),
+        Err(e) => return Err(e),
+      };
+
+      diesel::update(password_resets::table.find(&reset.id))
+        .set(password_resets::used_at.eq(Some(Utc::now())))
+        .execute(connection)?;
+
+      let user = users::table.find(&reset.user_id).first::<User>(connection)?;
+      let user = user.update_password(connection, password)?;
+      RefreshToken::revoke_all(connection, &user.id)?;
+
+      Ok(Some(user))
+    })
+  }
+}
+
+#[derive(Insertable)]
+#[table_name = "password_resets"]
+pub struct NewPasswordReset {
+  pub user_id: String,
+  pub token_hash: String,
+  pub expires_at: DateTime<Utc>,
+}
+
+impl NewPasswordReset {
+  /// Create the reset token for the user and return it, tokens that were
+  /// created before and not used yet stop working. Token lifetime is configured
+  /// by `PASSWORD_RESET_LIFETIME_IN_MINUTES`.
+  pub fn create(
+    connection: &crate::diesel::PgConnection,
+    user_id: &str,
+  ) -> Result<String, result::Error> {
+    let lifetime: i64 = dotenv::var("PASSWORD_RESET_LIFETIME_IN_MINUTES")
+      .ok()
+      .and_then(|minutes| minutes.parse().ok())
+      .unwrap_or(60);
+
+    let token = generate();
+
+    let values = Self {
+      user_id: String::from(user_id),
+      token_hash: hash(&token),
+      expires_at: Utc::now() + Duration::minutes(lifetime),
+    };
+
+    connection.transaction(|| {
+      let previous = password_resets::table
+        .filter(password_resets::user_id.eq(user_id))
+        .filter(password_resets::used_at.is_null());
+      diesel::update(previous)
+        .set(password_resets::expires_at.eq(Utc::now()))
+        .execute(connection)?;
+
+      diesel::insert_into(password_resets::table)
+        .values(&values)
+        .execute(connection)?;
+
+      Ok(token)
+    })
+  }
+}
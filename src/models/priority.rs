@@ -0,0 +1,53 @@
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::SmallInt;
+use std::io::Write;
+
+/// Priority of the todo, stored as a small integer in the database so
+/// ordering by the column follows the priority levels.
+#[derive(
+  AsExpression,
+  FromSqlRow,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Debug,
+  serde::Serialize,
+  serde::Deserialize,
+)]
+#[sql_type = "SmallInt"]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+  Low,
+  #[default]
+  Normal,
+  High,
+  Urgent,
+}
+
+impl ToSql<SmallInt, Pg> for Priority {
+  fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+    let value: i16 = match self {
+      Priority::Low => 0,
+      Priority::Normal => 1,
+      Priority::High => 2,
+      Priority::Urgent => 3,
+    };
+
+    ToSql::<SmallInt, Pg>::to_sql(&value, out)
+  }
+}
+
+impl FromSql<SmallInt, Pg> for Priority {
+  fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+    match <i16 as FromSql<SmallInt, Pg>>::from_sql(bytes)? {
+      0 => Ok(Priority::Low),
+      1 => Ok(Priority::Normal),
+      2 => Ok(Priority::High),
+      3 => Ok(Priority::Urgent),
+      value => Err(format!("Unknown priority: {}", value).into()),
+    }
+  }
+}
@@ -0,0 +1,229 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc, Weekday};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Largest interval between the occurrences that is accepted
+pub const MAX_INTERVAL: u32 = 1000;
+
+/// Number of days in each month, february counts with the leap years
+const MONTH_DAYS: [u32; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+/// How often the recurring todo repeats
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Frequency {
+  Daily,
+  Weekly,
+  Monthly,
+}
+
+/// Recurrence rule of the todo, supports a subset of the iCalendar RRULE:
+///
+/// - `FREQ=DAILY;INTERVAL=2` every other day
+/// - `FREQ=WEEKLY;BYDAY=MO,WE,FR` every monday, wednesday and friday
+/// - `FREQ=MONTHLY;BYMONTHDAY=15` on the fifteenth of every month
+#[derive(Clone, PartialEq, Debug)]
+pub struct Recurrence {
+  pub frequency: Frequency,
+  pub interval: u32,
+  pub weekdays: Vec<Weekday>,
+  pub month_day: Option<u32>,
+}
+
+impl FromStr for Recurrence {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let mut frequency = None;
+    let mut interval = 1;
+    let mut weekdays = vec![];
+    let mut month_day = None;
+
+    for part in value.trim().trim_start_matches("RRULE:").split(';') {
+      let mut pair = part.splitn(2, '=');
+      let key = pair.next().unwrap_or("").trim().to_uppercase();
+      let value = pair.next().unwrap_or("").trim().to_uppercase();
+
+      match key.as_str() {
+        "FREQ" => {
+          frequency = Some(match value.as_str() {
+            "DAILY" => Frequency::Daily,
+            "WEEKLY" => Frequency::Weekly,
+            "MONTHLY" => Frequency::Monthly,
+            _ => return Err(format!("Unsupported frequency: {}", value)),
+          })
+        }
+        "INTERVAL" => match value.parse::<u32>() {
+          Ok(n) if (1..=MAX_INTERVAL).contains(&n) => interval = n,
+          _ => return Err(format!("Invalid interval: {}", value)),
+        },
+        "BYDAY" => {
+          for day in value.split(',') {
+            weekdays.push(match day {
+              "MO" => Weekday::Mon,
+              "TU" => Weekday::Tue,
+              "WE" => Weekday::Wed,
+              "TH" => Weekday::Thu,
+              "FR" => Weekday::Fri,
+              "SA" => Weekday::Sat,
+              "SU" => Weekday::Sun,
+              _ => return Err(format!("Invalid weekday: {}", day)),
+            });
+          }
+        }
+        "BYMONTHDAY" => match value.parse::<u32>() {
+          Ok(n) if (1..=31).contains(&n) => month_day = Some(n),
+          _ => return Err(format!("Invalid month day: {}", value)),
+        },
+        _ => return Err(format!("Unsupported rule part: {}", part)),
+      }
+    }
+
+    let frequency = match frequency {
+      Some(frequency) => frequency,
+      None => return Err(String::from("Missing frequency")),
+    };
+
+    if !weekdays.is_empty() && frequency != Frequency::Weekly {
+      return Err(String::from("BYDAY is supported only for weekly rules"));
+    }
+
+    if month_day.is_some() && frequency != Frequency::Monthly {
+      return Err(String::from("BYMONTHDAY is supported only for monthly rules"));
+    }
+
+    // Stepping by the interval visits only some of the months, each of the
+    // possible sets of months has to contain one that has the day
+    if let Some(day) = month_day {
+      let step = gcd(interval, 12) as usize;
+      let unreachable = (0..step).any(|start| {
+        (start..12)
+          .step_by(step)
+          .all(|month| MONTH_DAYS[month] < day)
+      });
+
+      if unreachable {
+        return Err(format!("BYMONTHDAY={} never occurs with INTERVAL={}", day, interval));
+      }
+    }
+
+    Ok(Recurrence {
+      frequency,
+      interval,
+      weekdays,
+      month_day,
+    })
+  }
+}
+
+/// Greatest common divisor of the two numbers
+fn gcd(a: u32, b: u32) -> u32 {
+  if b == 0 {
+    a
+  } else {
+    gcd(b, a % b)
+  }
+}
+
+impl Recurrence {
+  /// Date of the occurrence that follows the one at the given date,
+  /// the time of the day is kept. None is returned when there is no
+  /// such date within the range of the supported dates.
+  pub fn next_after(&self, date: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let interval = self.interval as i64;
+
+    match self.frequency {
+      Frequency::Daily => date.checked_add_signed(Duration::days(interval)),
+      Frequency::Weekly if self.weekdays.is_empty() => {
+        date.checked_add_signed(Duration::weeks(interval))
+      }
+      Frequency::Weekly => {
+        let week_start = |d: DateTime<Utc>| {
+          d.date().naive_utc() - Duration::days(d.weekday().num_days_from_monday() as i64)
+        };
+
+        // One of the listed days is always found within the interval of weeks
+        (1..=7 * (interval + 1))
+          .filter_map(|days| date.checked_add_signed(Duration::days(days)))
+          .find(|next| {
+            let weeks = (week_start(*next) - week_start(date)).num_days() / 7;
+            weeks % interval == 0 && self.weekdays.contains(&next.weekday())
+          })
+      }
+      Frequency::Monthly => {
+        let day = self.month_day.unwrap_or_else(|| date.day());
+        let start = date.year() as i64 * 12 + date.month0() as i64;
+
+        // Months that don't have the day are skipped, e.g. the 31st
+        (0..=12 * interval)
+          .map(|step| start + step * interval)
+          .filter_map(|months| {
+            let year = i32::try_from(months.div_euclid(12)).ok()?;
+            let month = months.rem_euclid(12) as u32 + 1;
+
+            Utc
+              .ymd_opt(year, month, day)
+              .and_hms_opt(date.hour(), date.minute(), date.second())
+              .single()
+          })
+          .find(|next| *next > date)
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Frequency, Recurrence};
+  use chrono::{TimeZone, Utc};
+  #[test]
+  fn calculate_next_occurrence() {
+    // Wednesday
+    let date = Utc.ymd(2020, 10, 21).and_hms(9, 0, 0);
+
+    let daily: Recurrence = "FREQ=DAILY;INTERVAL=2".parse().unwrap();
+    assert_eq!(daily.next_after(date), Some(Utc.ymd(2020, 10, 23).and_hms(9, 0, 0)));
+
+    let weekly: Recurrence = "FREQ=WEEKLY;BYDAY=MO,WE".parse().unwrap();
+    assert_eq!(weekly.next_after(date), Some(Utc.ymd(2020, 10, 26).and_hms(9, 0, 0)));
+
+    let biweekly: Recurrence = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR".parse().unwrap();
+    assert_eq!(biweekly.next_after(date), Some(Utc.ymd(2020, 10, 23).and_hms(9, 0, 0)));
+
+    let monthly: Recurrence = "FREQ=MONTHLY;BYMONTHDAY=31".parse().unwrap();
+    assert_eq!(monthly.next_after(date), Some(Utc.ymd(2020, 10, 31).and_hms(9, 0, 0)));
+    assert_eq!(
+      monthly.next_after(Utc.ymd(2020, 10, 31).and_hms(9, 0, 0)),
+      Some(Utc.ymd(2020, 12, 31).and_hms(9, 0, 0))
+    );
+
+    let leap: Recurrence = "FREQ=MONTHLY;INTERVAL=12;BYMONTHDAY=29".parse().unwrap();
+    assert_eq!(
+      leap.next_after(Utc.ymd(2021, 2, 1).and_hms(9, 0, 0)),
+      Some(Utc.ymd(2024, 2, 29).and_hms(9, 0, 0))
+    );
+
+    assert!("FREQ=YEARLY".parse::<Recurrence>().is_err());
+    assert!("FREQ=DAILY;BYDAY=MO".parse::<Recurrence>().is_err());
+  }
+
+  #[test]
+  fn refuse_rules_that_never_occur() {
+    assert!("FREQ=MONTHLY;INTERVAL=12;BYMONTHDAY=31".parse::<Recurrence>().is_err());
+    assert!("FREQ=MONTHLY;INTERVAL=12;BYMONTHDAY=30".parse::<Recurrence>().is_err());
+    assert!("FREQ=MONTHLY;INTERVAL=6;BYMONTHDAY=31".parse::<Recurrence>().is_ok());
+    assert!("FREQ=DAILY;INTERVAL=4000000000".parse::<Recurrence>().is_err());
+    assert!("FREQ=DAILY;INTERVAL=1001".parse::<Recurrence>().is_err());
+
+    // Rules that made it into the database before are stopped as well
+    let never = Recurrence {
+      frequency: Frequency::Monthly,
+      interval: 12,
+      weekdays: vec![],
+      month_day: Some(31),
+    };
+    assert_eq!(never.next_after(Utc.ymd(2020, 4, 30).and_hms(9, 0, 0)), None);
+
+    let far: Recurrence = "FREQ=WEEKLY;INTERVAL=1000".parse().unwrap();
+    assert_eq!(far.next_after(Utc.ymd(262140, 1, 1).and_hms(9, 0, 0)), None);
+  }
+}
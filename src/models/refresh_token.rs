@@ -0,0 +1,197 @@
+use super::super::schema::{refresh_tokens, users};
+use super::user::User;
+use crate::services::jwt::JwtConfig;
+use crate::services::token::{generate, hash};
+use crate::diesel::ExpressionMethods;
+use crate::diesel::QueryDsl;
+use crate::diesel::RunQueryDsl;
+use chrono::{DateTime, Duration, Utc};
+use diesel::result;
+use diesel::Connection;
+use uuid::Uuid;
+
+/// Refresh token that was issued together with the access token with
+/// given jti. Only the hash of the refresh token is kept, revoking the
+/// refresh token revokes the access token as well.
+#[derive(Queryable, PartialEq, Debug)]
+pub struct RefreshToken {
+  pub id: String,
+  pub user_id: String,
+  pub token_hash: String,
+  pub jti: String,
+  pub expires_at: DateTime<Utc>,
+  pub revoked_at: Option<DateTime<Utc>>,
+  pub created_at: DateTime<Utc>,
+}
+
+/// Access token together with the refresh token that can be used to renew it
+#[derive(Debug)]
+pub struct TokenPair {
+  pub access_token: String,
+  pub refresh_token: String,
+}
+
+/// Reason why the tokens could not be issued
+#[derive(Debug)]
+pub enum IssueError {
+  Database(result::Error),
+  Jwt(jsonwebtoken::errors::Error),
+}
+
+impl From<result::Error> for IssueError {
+  fn from(e: result::Error) -> Self {
+    IssueError::Database(e)
+  }
+}
+
+impl From<jsonwebtoken::errors::Error> for IssueError {
+  fn from(e: jsonwebtoken::errors::Error) -> Self {
+    IssueError::Jwt(e)
+  }
+}
+
+impl RefreshToken {
+  /// Check if the access token with given jti was not revoked
+  pub fn is_active(
+    connection: &crate::diesel::PgConnection,
+    jti: &str,
+  ) -> Result<bool, result::Error> {
+    let active: i64 = refresh_tokens::table
+      .filter(refresh_tokens::jti.eq(jti))
+      .filter(refresh_tokens::revoked_at.is_null())
+      .count()
+      .get_result(connection)?;
+
+    Ok(active > 0)
+  }
+
+  /// Exchange the refresh token for the new pair of tokens, the used token is
+  /// revoked. Presenting already revoked token means that it might have been
+  /// stolen, so all the tokens of the user get revoked. None is returned when
+  /// the token can not be used.
+  pub fn rotate(
+    connection: &crate::diesel::PgConnection,
+    jwt: &JwtConfig,
+    refresh_token: &str,
+  ) -> Result<Option<(User, TokenPair)>, IssueError> {
+    connection.transaction::<_, IssueError, _>(|| {
+      let token = match refresh_tokens::table
+        .filter(refresh_tokens::token_hash.eq(hash(refresh_token)))
+        .for_update()
+        .first::<RefreshToken>(connection)
+      {
+        Ok(token) => token,
+        Err(result::Error::NotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+      };
+
+      if token.revoked_at.is_some() {
+        println!("Refresh token reused, revoking all tokens of {}", token.user_id);
+        Self::revoke_all(connection, &token.user_id)?;
+        return Ok(None);
+      }
+
+      if token.expires_at < Utc::now() {
+        return Ok(None);
+      }
+
+      diesel::update(refresh_tokens::table.find(&token.id))
+        .set(refresh_tokens::revoked_at.eq(Some(Utc::now())))
+        .execute(connection)?;
+
+      let user = users::table.find(&token.user_id).first::<User>(connection)?;
+      let pair = NewRefreshToken::issue(connection, jwt, &user)?;
+
+      Ok(Some((user, pair)))
+    })
+  }
+
+  /// Revoke the access token with given jti together with its refresh token
+  pub fn revoke(
+    connection: &crate::diesel::PgConnection,
+    jti: &str,
+  ) -> Result<usize, result::Error> {
+    let target = refresh_tokens::table
+      .filter(refresh_tokens::jti.eq(jti))
+      .filter(refresh_tokens::revoked_at.is_null());
+
+    diesel::update(target)
+      .set(refresh_tokens::revoked_at.eq(Some(Utc::now())))
+      .execute(connection)
+  }
+
+  /// Revoke all the tokens of the user, logging the user out everywhere
+  pub fn revoke_all(
+    connection: &crate::diesel::PgConnection,
+    user_id: &str,
+  ) -> Result<usize, result::Error> {
+    let target = refresh_tokens::table
+      .filter(refresh_tokens::user_id.eq(user_id))
+      .filter(refresh_tokens::revoked_at.is_null());
+
+    diesel::update(target)
+      .set(refresh_tokens::revoked_at.eq(Some(Utc::now())))
+      .execute(connection)
+  }
+
+  /// Revoke all the tokens of the user except the one with given jti,
+  /// logging the user out everywhere else
+  pub fn revoke_others(
+    connection: &crate::diesel::PgConnection,
+    user_id: &str,
+    jti: &str,
+  ) -> Result<usize, result::Error> {
+    let target = refresh_tokens::table
+      .filter(refresh_tokens::user_id.eq(user_id))
+      .filter(refresh_tokens::jti.ne(jti))
+      .filter(refresh_tokens::revoked_at.is_null());
+
+    diesel::update(target)
+      .set(refresh_tokens::revoked_at.eq(Some(Utc::now())))
+      .execute(connection)
+  }
+}
+
+#[derive(Insertable)]
+#[table_name = "refresh_tokens"]
+pub struct NewRefreshToken {
+  pub user_id: String,
+  pub token_hash: String,
+  pub jti: String,
+  pub expires_at: DateTime<Utc>,
+}
+
+impl NewRefreshToken {
+  /// Issue new access token for the user together with the refresh token,
+  /// refresh token lifetime is configured by `REFRESH_TOKEN_LIFETIME_IN_DAYS`
+  pub fn issue(
+    connection: &crate::diesel::PgConnection,
+    jwt: &JwtConfig,
+    user: &User,
+  ) -> Result<TokenPair, IssueError> {
+    let lifetime: i64 = dotenv::var("REFRESH_TOKEN_LIFETIME_IN_DAYS")
+      .ok()
+      .and_then(|days| days.parse().ok())
+      .unwrap_or(30);
+
+    let jti = Uuid::new_v4().to_string();
+    let access_token = user.generate_jwt(jwt, &jti)?;
+    let refresh_token = generate();
+
+    let values = Self {
+      user_id: String::from(&user.id),
+      token_hash: hash(&refresh_token),
+      jti: String::from(&jti),
+      expires_at: Utc::now() + Duration::days(lifetime),
+    };
+
+    diesel::insert_into(refresh_tokens::table)
+      .values(&values)
+      .execute(connection)?;
+
+    Ok(TokenPair {
+      access_token,
+      refresh_token,
+    })
+  }
+}
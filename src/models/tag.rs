@@ -0,0 +1,107 @@
+use super::super::schema::{tags, todo_tags};
+use crate::diesel::ExpressionMethods;
+use crate::diesel::QueryDsl;
+use crate::diesel::RunQueryDsl;
+use chrono::{DateTime, Utc};
+use diesel::result;
+
+/// Label that the user can attach to any number of his todos
+#[derive(Queryable, PartialEq, Debug, Clone, serde::Serialize)]
+pub struct Tag {
+  pub id: String,
+  pub user_id: String,
+  pub name: String,
+  pub created_at: DateTime<Utc>,
+}
+
+impl Tag {
+  /// Get all the tags of the user
+  pub fn users(
+    connection: &crate::diesel::PgConnection,
+    user_id: &str,
+  ) -> Result<Vec<Tag>, result::Error> {
+    tags::table
+      .filter(tags::user_id.eq(user_id))
+      .order((tags::name.asc(), tags::id.asc()))
+      .load::<Tag>(connection)
+  }
+
+  /// Get single tag out of the database
+  pub fn show(connection: &crate::diesel::PgConnection, id: &str) -> Result<Self, result::Error> {
+    tags::table.find(id).first::<Self>(connection)
+  }
+
+  /// Get tags attached to any of the given todos, paired with the todo id
+  /// so all of them can be loaded with a single query.
+  pub fn for_todos(
+    connection: &crate::diesel::PgConnection,
+    todo_ids: &[String],
+  ) -> Result<Vec<(String, Tag)>, result::Error> {
+    todo_tags::table
+      .inner_join(tags::table)
+      .filter(todo_tags::todo_id.eq_any(todo_ids))
+      .order((tags::name.asc(), tags::id.asc()))
+      .select((todo_tags::todo_id, tags::all_columns))
+      .load::<(String, Tag)>(connection)
+  }
+
+  /// Attach the tag to the todo, attaching it twice has no effect
+  pub fn attach(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    todo_id: &str,
+  ) -> Result<bool, result::Error> {
+    diesel::insert_into(todo_tags::table)
+      .values((
+        todo_tags::todo_id.eq(todo_id),
+        todo_tags::tag_id.eq(&self.id),
+      ))
+      .on_conflict_do_nothing()
+      .execute(connection)?;
+
+    Ok(true)
+  }
+
+  /// Detach the tag from the todo
+  pub fn detach(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    todo_id: &str,
+  ) -> Result<bool, result::Error> {
+    let target = todo_tags::table
+      .filter(todo_tags::todo_id.eq(todo_id))
+      .filter(todo_tags::tag_id.eq(&self.id));
+    let deleted = diesel::delete(target).execute(connection)?;
+
+    if deleted == 0 {
+      Err(result::Error::NotFound)
+    } else {
+      Ok(true)
+    }
+  }
+}
+
+#[derive(Insertable)]
+#[table_name = "tags"]
+pub struct NewTag {
+  pub user_id: String,
+  pub name: String,
+}
+
+impl NewTag {
+  /// Create new tag for the user
+  pub fn create<'a>(
+    connection: &crate::diesel::PgConnection,
+    user_id: &'a str,
+    name: &'a str,
+  ) -> Result<Tag, result::Error> {
+    let values = Self {
+      user_id: String::from(user_id),
+      name: String::from(name),
+    };
+
+    diesel::insert_into(tags::table)
+      .values(&values)
+      .get_result::<Tag>(connection)
+  }
+}
@@ -1,35 +1,550 @@
-use super::super::schema::todos;
+use super::super::schema::{list_members, lists, todo_tags, todos, users};
+use super::attachment::Attachment;
+use super::priority::Priority;
+use super::recurrence::Recurrence;
+use super::tag::Tag;
+use super::todo_event::{EventKind, NewTodoEvent};
+use super::user::User;
 use super::Paginated;
+use crate::services::authorization::{self, Access};
+use crate::diesel::BoolExpressionMethods;
 use crate::diesel::ExpressionMethods;
+use crate::diesel::NullableExpressionMethods;
 use crate::diesel::QueryDsl;
 use crate::diesel::RunQueryDsl;
+use chrono::{DateTime, Utc};
+use diesel::expression::dsl::{max, min};
+use diesel::pg::Pg;
+use diesel::Connection;
 use diesel::result;
+use diesel::sql_query;
+use diesel::expression::{AsExpression, BoxableExpression};
+use diesel::sql_types::{BigInt, Bool, Float4, Text};
+use diesel::OptionalExtension;
+use serde_json::json;
+use std::collections::HashMap;
+use std::str::FromStr;
 
-#[derive(Queryable, PartialEq, Debug, serde::Serialize)]
+#[derive(Queryable, QueryableByName, PartialEq, Debug, serde::Serialize)]
+#[table_name = "todos"]
 pub struct Todo {
   pub id: String,
   pub user_id: String,
   pub content: String,
   pub checked: bool,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+  pub completed_at: Option<DateTime<Utc>>,
+  pub due_at: Option<DateTime<Utc>>,
+  #[serde(skip_serializing)]
+  pub reminded_at: Option<DateTime<Utc>>,
+  pub priority: Priority,
+  pub position: f64,
+  pub list_id: Option<String>,
+  pub parent_id: Option<String>,
+  pub recurrence: Option<String>,
+  pub series_id: Option<String>,
+  pub deleted_at: Option<DateTime<Utc>>,
+  pub assignee_id: Option<String>,
+}
+
+/// Progress of the subtasks of the todo
+#[derive(PartialEq, Debug, serde::Serialize)]
+pub struct Progress {
+  pub done: i64,
+  pub total: i64,
+}
+
+impl Progress {
+  /// Count the subtasks and how many of them are done
+  pub fn of(subtasks: &[Todo]) -> Self {
+    Progress {
+      done: subtasks.iter().filter(|subtask| subtask.checked).count() as i64,
+      total: subtasks.len() as i64,
+    }
+  }
+}
+
+/// Todo together with the tags attached to it, and the progress on its
+/// subtasks when it has any
+#[derive(PartialEq, Debug, serde::Serialize)]
+pub struct TodoWithTags {
+  #[serde(flatten)]
+  pub todo: Todo,
+  pub tags: Vec<Tag>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub progress: Option<Progress>,
+}
+
+impl TodoWithTags {
+  /// Attach the tags and the progress to each of the todos, all the tags and
+  /// the subtasks are loaded with a single query regardless of the number of todos.
+  pub fn load(
+    connection: &crate::diesel::PgConnection,
+    todos: Vec<Todo>,
+  ) -> Result<Vec<TodoWithTags>, result::Error> {
+    let ids: Vec<String> = todos.iter().map(|todo| todo.id.clone()).collect();
+    let mut tags: HashMap<String, Vec<Tag>> = HashMap::new();
+    let mut progress: HashMap<String, Progress> = HashMap::new();
+
+    for (todo_id, tag) in Tag::for_todos(connection, &ids)? {
+      tags.entry(todo_id).or_default().push(tag);
+    }
+
+    let subtasks = todos::table
+      .filter(todos::parent_id.eq_any(&ids))
+      .filter(todos::deleted_at.is_null())
+      .select((todos::parent_id, todos::checked))
+      .load::<(Option<String>, bool)>(connection)?;
+
+    for (parent_id, checked) in subtasks {
+      if let Some(parent_id) = parent_id {
+        let entry = progress.entry(parent_id).or_insert(Progress { done: 0, total: 0 });
+        entry.total += 1;
+        if checked {
+          entry.done += 1;
+        }
+      }
+    }
+
+    Ok(
+      todos
+        .into_iter()
+        .map(|todo| TodoWithTags {
+          tags: tags.remove(&todo.id).unwrap_or_default(),
+          progress: progress.remove(&todo.id),
+          todo,
+        })
+        .collect(),
+    )
+  }
+}
+
+/// Todo that matched the full text search, with the rank of the match
+/// and the snippet of the content where the matched words are highlighted
+#[derive(PartialEq, Debug, serde::Serialize)]
+pub struct SearchResult {
+  #[serde(flatten)]
+  pub todo: TodoWithTags,
+  pub rank: f32,
+  pub snippet: String,
+}
+
+/// Temporary struct for incoming data from the search sql query
+#[derive(QueryableByName)]
+struct SearchRow {
+  #[diesel(embed)]
+  todo: Todo,
+  #[sql_type = "Float4"]
+  rank: f32,
+  #[sql_type = "Text"]
+  snippet: String,
+}
+
+/// Temporary struct for incoming count from the search sql query
+#[derive(QueryableByName)]
+struct SearchCount {
+  #[sql_type = "BigInt"]
+  count: i64,
+}
+
+/// Whether the listed todos need to have any or all of the filtered tags
+#[derive(Clone, Copy, PartialEq, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatch {
+  #[default]
+  Any,
+  All,
+}
+
+/// Completion status of the listed todos
+#[derive(Clone, Copy, PartialEq, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TodoStatus {
+  All,
+  #[default]
+  Open,
+  Done,
+}
+
+/// Filters that can be applied when listing the todos of the user
+#[derive(Default)]
+pub struct TodoFilter {
+  pub status: TodoStatus,
+  pub overdue: bool,
+  pub created_before: Option<DateTime<Utc>>,
+  pub created_after: Option<DateTime<Utc>>,
+  pub due_before: Option<DateTime<Utc>>,
+  pub due_after: Option<DateTime<Utc>>,
+  pub list_id: Option<String>,
+  pub priority: Option<Priority>,
+  pub assignee_id: Option<String>,
+  pub tags: Vec<String>,
+  pub tag_match: TagMatch,
+  pub include_subtasks: bool,
+}
+
+impl TodoFilter {
+  /// Build the query for the todos accessible to the user with the filters
+  /// applied, the query can be further narrowed, sorted or counted by the caller
+  pub fn query<'a>(&self, user_id: &'a str) -> todos::BoxedQuery<'a, Pg> {
+    let owned_lists = lists::table
+      .filter(lists::user_id.eq(user_id))
+      .select(lists::id.nullable());
+    let shared_lists = list_members::table
+      .filter(list_members::user_id.eq(user_id))
+      .select(list_members::list_id.nullable());
+
+    let mut query = todos::table
+      .filter(
+        todos::user_id
+          .eq(user_id)
+          .or(todos::assignee_id.eq(user_id))
+          .or(todos::list_id.eq_any(owned_lists))
+          .or(todos::list_id.eq_any(shared_lists)),
+      )
+      .filter(todos::deleted_at.is_null())
+      .into_boxed();
+
+    // Subtasks are listed under their parent unless asked for
+    if !self.include_subtasks {
+      query = query.filter(todos::parent_id.is_null());
+    }
+
+    query = match self.status {
+      TodoStatus::All => query,
+      TodoStatus::Open => query.filter(todos::checked.eq(false)),
+      TodoStatus::Done => query.filter(todos::checked.eq(true)),
+    };
+
+    if self.overdue {
+      query = query
+        .filter(todos::checked.eq(false))
+        .filter(todos::due_at.lt(Utc::now()));
+    }
+
+    if let Some(before) = self.created_before {
+      query = query.filter(todos::created_at.lt(before));
+    }
+
+    if let Some(after) = self.created_after {
+      query = query.filter(todos::created_at.gt(after));
+    }
+
+    if let Some(before) = self.due_before {
+      query = query.filter(todos::due_at.lt(before));
+    }
+
+    if let Some(after) = self.due_after {
+      query = query.filter(todos::due_at.gt(after));
+    }
+
+    if let Some(list_id) = &self.list_id {
+      query = query.filter(todos::list_id.eq(list_id.clone()));
+    }
+
+    if let Some(priority) = self.priority {
+      query = query.filter(todos::priority.eq(priority));
+    }
+
+    if let Some(assignee_id) = &self.assignee_id {
+      query = query.filter(todos::assignee_id.eq(assignee_id.clone()));
+    }
+
+    if !self.tags.is_empty() {
+      match self.tag_match {
+        TagMatch::Any => {
+          let tagged = todo_tags::table
+            .filter(todo_tags::tag_id.eq_any(self.tags.clone()))
+            .select(todo_tags::todo_id);
+          query = query.filter(todos::id.eq_any(tagged));
+        }
+        TagMatch::All => {
+          for tag_id in &self.tags {
+            let tagged = todo_tags::table
+              .filter(todo_tags::tag_id.eq(tag_id.clone()))
+              .select(todo_tags::todo_id);
+            query = query.filter(todos::id.eq_any(tagged));
+          }
+        }
+      }
+    }
+
+    query
+  }
+}
+
+/// Field that the listed todos can be sorted by
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SortField {
+  Priority,
+  CreatedAt,
+  DueAt,
+  Content,
+  Position,
+}
+
+/// Sorting of the listed todos, parsed from the field name which is
+/// prefixed with `-` for the descending order, e.g. `-priority`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TodoSort {
+  pub field: SortField,
+  pub descending: bool,
+}
+
+impl Default for TodoSort {
+  fn default() -> Self {
+    TodoSort {
+      field: SortField::CreatedAt,
+      descending: false,
+    }
+  }
+}
+
+impl FromStr for TodoSort {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let (descending, name) = match value.strip_prefix('-') {
+      Some(name) => (true, name),
+      None => (false, value),
+    };
+
+    let field = match name {
+      "priority" => SortField::Priority,
+      "created_at" => SortField::CreatedAt,
+      "due_at" => SortField::DueAt,
+      "content" => SortField::Content,
+      "position" => SortField::Position,
+      _ => return Err(format!("Unknown sort field: {}", name)),
+    };
+
+    Ok(TodoSort { field, descending })
+  }
+}
+
+impl TodoSort {
+  /// Apply the ordering to the query, the id is always used as the last
+  /// ordering so the todos with equal values keep stable positions.
+  fn apply<'a>(&self, query: todos::BoxedQuery<'a, Pg>) -> todos::BoxedQuery<'a, Pg> {
+    let query = match (self.field, self.descending) {
+      (SortField::Priority, false) => query.order(todos::priority.asc()),
+      (SortField::Priority, true) => query.order(todos::priority.desc()),
+      (SortField::CreatedAt, false) => query.order(todos::created_at.asc()),
+      (SortField::CreatedAt, true) => query.order(todos::created_at.desc()),
+      (SortField::DueAt, false) => query.order(todos::due_at.asc()),
+      (SortField::DueAt, true) => query.order(todos::due_at.desc()),
+      (SortField::Content, false) => query.order(todos::content.asc()),
+      (SortField::Content, true) => query.order(todos::content.desc()),
+      (SortField::Position, false) => query.order(todos::position.asc()),
+      (SortField::Position, true) => query.order(todos::position.desc()),
+    };
+
+    if self.descending {
+      query.then_order_by(todos::id.desc())
+    } else {
+      query.then_order_by(todos::id.asc())
+    }
+  }
+}
+
+/// Sort values of the last todo on the page, cursor continues right after them
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CursorKey {
+  Priority(Priority),
+  CreatedAt(DateTime<Utc>),
+  DueAt(Option<DateTime<Utc>>),
+  Content(String),
+  Position(f64),
+}
+
+/// Opaque position in the sorted todos, handed out to the client as a string
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct TodoCursor {
+  page: u32,
+  key: CursorKey,
+  descending: bool,
+  id: String,
+}
+
+impl TodoCursor {
+  /// Cursor pointing right after the todo in the given sorting
+  fn after(todo: &Todo, sort: &TodoSort, page: u32) -> Self {
+    let key = match sort.field {
+      SortField::Priority => CursorKey::Priority(todo.priority),
+      SortField::CreatedAt => CursorKey::CreatedAt(todo.created_at),
+      SortField::DueAt => CursorKey::DueAt(todo.due_at),
+      SortField::Content => CursorKey::Content(todo.content.clone()),
+      SortField::Position => CursorKey::Position(todo.position),
+    };
+
+    TodoCursor {
+      page,
+      key,
+      descending: sort.descending,
+      id: todo.id.clone(),
+    }
+  }
+
+  /// Encode the cursor into the url safe string
+  pub fn encode(&self) -> String {
+    let json = serde_json::to_vec(self).unwrap_or_default();
+    base64::encode_config(json, base64::URL_SAFE_NO_PAD)
+  }
+
+  /// Decode the cursor previously handed out to the client
+  pub fn decode(value: &str) -> Result<Self, String> {
+    let json = base64::decode_config(value, base64::URL_SAFE_NO_PAD)
+      .map_err(|_| String::from("Malformed cursor"))?;
+    serde_json::from_slice(&json).map_err(|_| String::from("Malformed cursor"))
+  }
+
+  /// Sorting that the cursor was issued for
+  pub fn sort(&self) -> TodoSort {
+    let field = match self.key {
+      CursorKey::Priority(_) => SortField::Priority,
+      CursorKey::CreatedAt(_) => SortField::CreatedAt,
+      CursorKey::DueAt(_) => SortField::DueAt,
+      CursorKey::Content(_) => SortField::Content,
+      CursorKey::Position(_) => SortField::Position,
+    };
+
+    TodoSort {
+      field,
+      descending: self.descending,
+    }
+  }
+
+  /// Condition matching the todos that come after the cursor, mirrors the
+  /// ordering of `TodoSort::apply` including the id tie-breaker.
+  fn condition(&self) -> Box<dyn BoxableExpression<todos::table, Pg, SqlType = Bool>> {
+    let id = self.id.clone();
+    let descending = self.descending;
+
+    match &self.key {
+      CursorKey::Priority(priority) => keyset(todos::priority, *priority, id, descending),
+      CursorKey::CreatedAt(created_at) => keyset(todos::created_at, *created_at, id, descending),
+      CursorKey::Content(content) => keyset(todos::content, content.clone(), id, descending),
+      CursorKey::Position(position) => keyset(todos::position, *position, id, descending),
+      // Postgres places the missing due dates last in the ascending order
+      // and first in the descending one
+      CursorKey::DueAt(Some(due_at)) => {
+        let after = keyset(todos::due_at, *due_at, id, descending);
+        if descending {
+          after
+        } else {
+          Box::new(after.or(todos::due_at.is_null()))
+        }
+      }
+      CursorKey::DueAt(None) => {
+        if descending {
+          Box::new(
+            todos::due_at
+              .is_null()
+              .and(todos::id.lt(id))
+              .or(todos::due_at.is_not_null()),
+          )
+        } else {
+          Box::new(todos::due_at.is_null().and(todos::id.gt(id)))
+        }
+      }
+    }
+  }
+}
+
+/// Rows after the value in the column, equal values are ordered by the id
+fn keyset<C, V>(
+  column: C,
+  value: V,
+  id: String,
+  descending: bool,
+) -> Box<dyn BoxableExpression<todos::table, Pg, SqlType = Bool>>
+where
+  C: ExpressionMethods + BoxableExpression<todos::table, Pg> + Copy + 'static,
+  V: AsExpression<C::SqlType> + Clone,
+  V::Expression: BoxableExpression<todos::table, Pg> + 'static,
+{
+  if descending {
+    Box::new(
+      column
+        .lt(value.clone())
+        .or(column.eq(value).and(todos::id.lt(id))),
+    )
+  } else {
+    Box::new(
+      column
+        .gt(value.clone())
+        .or(column.eq(value).and(todos::id.gt(id))),
+    )
+  }
+}
+
+/// Requested page of the todos, either by its number or by the cursor
+#[derive(Clone, PartialEq, Debug)]
+pub enum TodoPage {
+  Number(u32),
+  After(TodoCursor),
+}
+
+/// Action applied to every todo of the bulk operation
+#[derive(Clone, PartialEq, Debug, serde::Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BulkAction {
+  Check,
+  Uncheck,
+  Delete,
+  Move { list_id: Option<String> },
+  Tag { tag_id: String },
+}
+
+/// Reason why the bulk action failed for a single todo
+#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkError {
+  NotFound,
+  Forbidden,
+  Failed,
+}
+
+impl From<result::Error> for BulkError {
+  fn from(error: result::Error) -> Self {
+    match error {
+      result::Error::NotFound => BulkError::NotFound,
+      _ => BulkError::Failed,
+    }
+  }
+}
+
+/// Todo that the bulk action could not be applied to
+#[derive(PartialEq, Debug, serde::Serialize)]
+pub struct BulkFailure {
+  pub id: String,
+  pub error: BulkError,
+}
+
+/// Outcome of the bulk operation, split into succeeded and failed todo ids
+#[derive(PartialEq, Debug, Default, serde::Serialize)]
+pub struct BulkOutcome {
+  pub succeeded: Vec<String>,
+  pub failed: Vec<BulkFailure>,
 }
 
 impl Todo {
-  /// Get paginated todos for user
+  /// Get paginated todos for user, `next_cursor` is set whenever there are
+  /// more todos after the returned page
   pub fn paginated(
     connection: &crate::diesel::PgConnection,
-    page: u32,
+    page: &TodoPage,
     per_page: u32,
     user_id: String,
-    checked: bool,
-  ) -> Result<Paginated<Todo>, result::Error> {
-    let mut last_page = 1;
-    let mut data: Vec<Todo> = vec![];
+    filter: &TodoFilter,
+    sort: &TodoSort,
+  ) -> Result<Paginated<TodoWithTags>, result::Error> {
+    let mut data: Vec<TodoWithTags> = vec![];
+    let mut next_cursor = None;
 
-    let total = todos::table
-      .filter(todos::user_id.eq(&user_id))
-      .filter(todos::checked.eq(&checked))
-      .count()
-      .get_result(connection);
+    let total = filter.query(&user_id).count().get_result(connection);
 
     let total = match total {
       Ok(count) => count,
@@ -39,31 +554,147 @@ impl Todo {
       }
     };
 
+    let number = match page {
+      TodoPage::Number(number) => *number,
+      TodoPage::After(cursor) => cursor.page,
+    };
+
     if total > 0 {
-      last_page = total as u32 / per_page;
-      let skip = (page - 1) * per_page;
+      let query = sort.apply(filter.query(&user_id));
+      let query = match page {
+        TodoPage::Number(number) => query.offset((*number as i64 - 1) * per_page as i64),
+        TodoPage::After(cursor) => query.filter(cursor.condition()),
+      };
 
-      data = todos::table
-        .filter(todos::user_id.eq(user_id))
-        .filter(todos::checked.eq(checked))
-        .offset(skip as i64)
-        .limit(per_page.clone() as i64)
+      // One extra todo tells whether there is anything after this page
+      let mut todos = query
+        .limit(per_page as i64 + 1)
         .load::<Todo>(connection)?;
+
+      if todos.len() > per_page as usize {
+        todos.truncate(per_page as usize);
+        next_cursor = todos
+          .last()
+          .map(|todo| TodoCursor::after(todo, sort, number + 1).encode());
+      }
+
+      data = TodoWithTags::load(connection, todos)?;
+    }
+
+    Ok(Paginated::new(number, per_page, total as u32, data).with_next_cursor(next_cursor))
+  }
+
+  /// Search the user todos by their content, best matches come first
+  pub fn search(
+    connection: &crate::diesel::PgConnection,
+    page: u32,
+    per_page: u32,
+    user_id: &str,
+    text: &str,
+  ) -> Result<Paginated<SearchResult>, result::Error> {
+    let mut data: Vec<SearchResult> = vec![];
+
+    let total = sql_query(include_str!("../../sql/count_search_todos.sql"))
+      .bind::<Text, _>(user_id)
+      .bind::<Text, _>(text)
+      .get_result::<SearchCount>(connection)?
+      .count;
+
+    if total > 0 {
+      let skip = (page as i64 - 1) * per_page as i64;
+
+      let rows = sql_query(include_str!("../../sql/search_todos.sql"))
+        .bind::<Text, _>(user_id)
+        .bind::<Text, _>(text)
+        .bind::<BigInt, _>(per_page as i64)
+        .bind::<BigInt, _>(skip)
+        .load::<SearchRow>(connection)?;
+
+      let mut matches = vec![];
+      let mut todos = vec![];
+      for row in rows {
+        matches.push((row.rank, row.snippet));
+        todos.push(row.todo);
+      }
+
+      data = TodoWithTags::load(connection, todos)?
+        .into_iter()
+        .zip(matches)
+        .map(|(todo, (rank, snippet))| SearchResult {
+          todo,
+          rank,
+          snippet,
+        })
+        .collect();
     }
 
-    Ok(Paginated {
-      page: page,
-      per_page: per_page,
-      total: total as u32,
-      last_page,
-      data,
+    Ok(Paginated::new(page, per_page, total as u32, data))
+  }
+
+  /// Apply the action to every todo with given id in a single transaction.
+  /// Each todo gets its own savepoint, so the failed ones are rolled back
+  /// without affecting the rest.
+  pub fn bulk(
+    connection: &crate::diesel::PgConnection,
+    user_id: &str,
+    ids: &[String],
+    action: &BulkAction,
+  ) -> Result<BulkOutcome, result::Error> {
+    connection.transaction(|| {
+      let tag = match action {
+        BulkAction::Tag { tag_id } => Some(Tag::show(connection, tag_id)?),
+        _ => None,
+      };
+
+      let mut outcome = BulkOutcome::default();
+
+      for id in ids {
+        let applied = connection.transaction::<_, BulkError, _>(|| {
+          let todo = Self::show(connection, id)?;
+
+          let access = match action {
+            BulkAction::Check | BulkAction::Uncheck => Access::Complete,
+            _ => Access::Edit,
+          };
+          if !authorization::can_access_todo(connection, &todo, user_id, access)? {
+            return Err(BulkError::Forbidden);
+          }
+
+          match action {
+            BulkAction::Check => todo.check(connection, user_id, false).map(|_| ())?,
+            BulkAction::Uncheck => todo.uncheck(connection, user_id).map(|_| ())?,
+            BulkAction::Delete => todo.delete(connection, user_id).map(|_| ())?,
+            BulkAction::Move { list_id } => todo
+              .move_to_list(connection, user_id, list_id.as_deref())
+              .map(|_| ())?,
+            BulkAction::Tag { .. } => {
+              if let Some(tag) = &tag {
+                tag.attach(connection, &todo.id)?;
+              }
+            }
+          }
+
+          Ok(())
+        });
+
+        match applied {
+          Ok(_) => outcome.succeeded.push(id.clone()),
+          Err(error) => outcome.failed.push(BulkFailure {
+            id: id.clone(),
+            error,
+          }),
+        }
+      }
+
+      Ok(outcome)
     })
   }
 
-  /// Get single todo out of the database
+  /// Get single todo out of the database, trashed todos are not found
   pub fn show(connection: &crate::diesel::PgConnection, id: &str) -> Result<Self, result::Error> {
     let mut results = todos::table
       .filter(todos::id.eq(&id))
+      .filter(todos::deleted_at.is_null())
       .load::<Self>(connection)?;
 
     match results.pop() {
@@ -72,6 +703,115 @@ impl Todo {
     }
   }
 
+  /// Get single todo whether it is in the trash or not
+  pub fn show_with_trashed(
+    connection: &crate::diesel::PgConnection,
+    id: &str,
+  ) -> Result<Self, result::Error> {
+    todos::table.filter(todos::id.eq(&id)).first::<Self>(connection)
+  }
+
+  /// Get single todo out of the trash
+  pub fn show_trashed(
+    connection: &crate::diesel::PgConnection,
+    id: &str,
+  ) -> Result<Self, result::Error> {
+    todos::table
+      .filter(todos::id.eq(&id))
+      .filter(todos::deleted_at.is_not_null())
+      .first::<Self>(connection)
+  }
+
+  /// Get paginated trashed todos for user, the latest trashed come first
+  pub fn trashed(
+    connection: &crate::diesel::PgConnection,
+    page: u32,
+    per_page: u32,
+    user_id: &str,
+  ) -> Result<Paginated<TodoWithTags>, result::Error> {
+    let mut data: Vec<TodoWithTags> = vec![];
+
+    let total: i64 = todos::table
+      .filter(todos::user_id.eq(user_id))
+      .filter(todos::deleted_at.is_not_null())
+      .count()
+      .get_result(connection)?;
+
+    if total > 0 {
+      let skip = (page as i64 - 1) * per_page as i64;
+
+      let todos = todos::table
+        .filter(todos::user_id.eq(user_id))
+        .filter(todos::deleted_at.is_not_null())
+        .order((todos::deleted_at.desc(), todos::id.desc()))
+        .offset(skip)
+        .limit(per_page as i64)
+        .load::<Todo>(connection)?;
+
+      data = TodoWithTags::load(connection, todos)?;
+    }
+
+    Ok(Paginated::new(page, per_page, total as u32, data))
+  }
+
+  /// Remove the todos that are in the trash since before given time for good.
+  /// Subtasks that are not purged together with their parent are detached
+  /// from it, so they are not removed along with it. Number of the purged
+  /// todos is returned together with the storage keys of their attachments.
+  pub fn purge_trashed(
+    connection: &crate::diesel::PgConnection,
+    before: DateTime<Utc>,
+  ) -> Result<(usize, Vec<String>), result::Error> {
+    connection.transaction(|| {
+      let ids = Self::purgeable_ids(connection, before)?;
+      let storage_keys = Attachment::storage_keys(connection, &ids)?;
+
+      let children = todos::table
+        .filter(todos::parent_id.eq_any(&ids))
+        .filter(todos::id.ne_all(&ids));
+      diesel::update(children)
+        .set(todos::parent_id.eq(None::<String>))
+        .execute(connection)?;
+
+      let purged =
+        diesel::delete(todos::table.filter(todos::id.eq_any(&ids))).execute(connection)?;
+
+      Ok((purged, storage_keys))
+    })
+  }
+
+  /// Get ids of the todos that are in the trash since before given time. The
+  /// latest occurrence of a recurring series is kept, so the skipped
+  /// occurrence is not created again once it is gone.
+  fn purgeable_ids(
+    connection: &crate::diesel::PgConnection,
+    before: DateTime<Utc>,
+  ) -> Result<Vec<String>, result::Error> {
+    let skipped: Vec<String> = Self::latest_occurrences(connection)?
+      .into_iter()
+      .filter(|todo| todo.deleted_at.is_some())
+      .map(|todo| todo.id)
+      .collect();
+
+    todos::table
+      .filter(todos::deleted_at.lt(before))
+      .filter(todos::id.ne_all(skipped))
+      .select(todos::id)
+      .for_update()
+      .load::<String>(connection)
+  }
+
+  /// Attach the tags to the todo
+  pub fn with_tags(
+    self,
+    connection: &crate::diesel::PgConnection,
+  ) -> Result<TodoWithTags, result::Error> {
+    match TodoWithTags::load(connection, vec![self])?.pop() {
+      Some(todo) => Ok(todo),
+      None => Err(result::Error::NotFound),
+    }
+  }
+
   /// Get todos for single user
   pub fn users(
     connection: &crate::diesel::PgConnection,
@@ -79,35 +819,608 @@ impl Todo {
   ) -> Result<Vec<Todo>, result::Error> {
     todos::table
       .filter(todos::user_id.eq(&user_id))
+      .filter(todos::deleted_at.is_null())
       .load::<Todo>(connection)
   }
 
-  // Check the todo as done
-  pub fn check(&self, connection: &crate::diesel::PgConnection) -> Result<bool, result::Error> {
-    self.check_as(connection, true)
+  /// Get unchecked todos together with their owners that are going to be
+  /// due before given time and nobody was reminded about them yet
+  pub fn due_for_reminder(
+    connection: &crate::diesel::PgConnection,
+    until: DateTime<Utc>,
+  ) -> Result<Vec<(Todo, User)>, result::Error> {
+    todos::table
+      .inner_join(users::table)
+      .filter(todos::checked.eq(false))
+      .filter(todos::deleted_at.is_null())
+      .filter(todos::reminded_at.is_null())
+      .filter(todos::due_at.gt(Utc::now()))
+      .filter(todos::due_at.le(until))
+      .load::<(Todo, User)>(connection)
   }
 
-  // Remove done check for the todo
-  pub fn uncheck(&self, connection: &crate::diesel::PgConnection) -> Result<bool, result::Error> {
-    self.check_as(connection, false)
+  /// Mark the todo as reminded, returns false when some other run
+  /// has already done so, in which case the reminder shouldn't be sent.
+  pub fn mark_reminded(
+    &self,
+    connection: &crate::diesel::PgConnection,
+  ) -> Result<bool, result::Error> {
+    let target = todos::table
+      .filter(todos::id.eq(&self.id))
+      .filter(todos::reminded_at.is_null());
+    let updated = diesel::update(target)
+      .set(todos::reminded_at.eq(Utc::now()))
+      .execute(connection)?;
+
+    Ok(updated > 0)
   }
 
-  /// Check or uncheck the Todo
-  fn check_as(
+  /// Clear the reminder mark so the todo is picked up by the next run
+  pub fn unmark_reminded(
     &self,
     connection: &crate::diesel::PgConnection,
-    value: bool,
   ) -> Result<bool, result::Error> {
     let target = todos::table.filter(todos::id.eq(&self.id));
     let updated = diesel::update(target)
-      .set(todos::checked.eq(value))
+      .set(todos::reminded_at.eq(None::<DateTime<Utc>>))
       .execute(connection)?;
 
-    if updated <= 0 {
-      Err(result::Error::NotFound)
-    } else {
-      Ok(true)
+    Ok(updated > 0)
+  }
+
+  /// Get direct subtasks of the todo in their manual order
+  pub fn subtasks(
+    &self,
+    connection: &crate::diesel::PgConnection,
+  ) -> Result<Vec<Todo>, result::Error> {
+    todos::table
+      .filter(todos::parent_id.eq(&self.id))
+      .filter(todos::deleted_at.is_null())
+      .order((todos::position.asc(), todos::id.asc()))
+      .load::<Todo>(connection)
+  }
+
+  /// Ids of all the subtasks of the todo, including the nested ones
+  fn descendant_ids(
+    &self,
+    connection: &crate::diesel::PgConnection,
+  ) -> Result<Vec<String>, result::Error> {
+    let mut ids = vec![];
+    let mut parents = vec![self.id.clone()];
+
+    while !parents.is_empty() {
+      parents = todos::table
+        .filter(todos::parent_id.eq_any(parents))
+        .select(todos::id)
+        .load::<String>(connection)?;
+      ids.extend(parents.iter().cloned());
+    }
+
+    Ok(ids)
+  }
+
+  // Check the todo as done, when cascading all of its subtasks are checked too
+  pub fn check(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    actor_id: &str,
+    cascade: bool,
+  ) -> Result<Todo, result::Error> {
+    connection.transaction(|| {
+      if cascade {
+        let mut ids = vec![];
+        for subtask in todos::table
+          .filter(todos::id.eq_any(self.descendant_ids(connection)?))
+          .filter(todos::deleted_at.is_null())
+          .filter(todos::checked.eq(false))
+          .load::<Todo>(connection)?
+        {
+          // Only the subtasks the user is allowed to complete are checked
+          if authorization::can_access_todo(connection, &subtask, actor_id, Access::Complete)? {
+            ids.push(subtask.id);
+          }
+        }
+
+        let target = todos::table.filter(todos::id.eq_any(ids));
+        let subtasks = diesel::update(target)
+          .set((
+            todos::checked.eq(true),
+            todos::completed_at.eq(Some(Utc::now())),
+          ))
+          .get_results::<Todo>(connection)?;
+
+        for subtask in subtasks {
+          NewTodoEvent::new(&subtask.id, Some(actor_id), EventKind::Check)
+            .change(Some(json!({ "checked": false })), Some(json!({ "checked": true })))
+            .record(connection)?;
+        }
+      }
+
+      let checked = self.check_as(connection, actor_id, true)?;
+      checked.schedule_next(connection)?;
+
+      Ok(checked)
+    })
+  }
+
+  /// Create the occurrence of the recurring todo that follows this one,
+  /// none is returned when the todo doesn't recur or the next occurrence
+  /// has already been created.
+  pub fn schedule_next(
+    &self,
+    connection: &crate::diesel::PgConnection,
+  ) -> Result<Option<Todo>, result::Error> {
+    let (recurrence, due_at) = match (&self.recurrence, self.due_at) {
+      (Some(rule), Some(due_at)) => match rule.parse::<Recurrence>() {
+        Ok(recurrence) => (recurrence, due_at),
+        Err(e) => {
+          println!("Todos: Invalid recurrence of {}: {}", self.id, e);
+          return Ok(None);
+        }
+      },
+      _ => return Ok(None),
+    };
+
+    // Rule that has no more dates ends the series
+    let next_due_at = match recurrence.next_after(due_at) {
+      Some(next_due_at) => next_due_at,
+      None => return Ok(None),
+    };
+
+    let mut next = NewTodo::new(&self.user_id, &self.content);
+    next.due_at = Some(next_due_at);
+    next.priority = self.priority;
+    next.list_id = self.list_id.clone();
+    next.parent_id = self.parent_id.clone();
+    next.recurrence = self.recurrence.clone();
+    next.series_id = Some(self.series_id.clone().unwrap_or_else(|| self.id.clone()));
+
+    next.insert_occurrence(connection)
+  }
+
+  /// Get the latest occurrence of every recurring todo series. Occurrences in
+  /// the trash count as well, the user skipped them so they are not created again.
+  pub fn latest_occurrences(
+    connection: &crate::diesel::PgConnection,
+  ) -> Result<Vec<Todo>, result::Error> {
+    todos::table
+      .filter(todos::recurrence.is_not_null())
+      .filter(todos::series_id.is_not_null())
+      .filter(todos::due_at.is_not_null())
+      .distinct_on(todos::series_id)
+      .order((todos::series_id.asc(), todos::due_at.desc()))
+      .load::<Todo>(connection)
+  }
+
+  // Remove done check for the todo
+  pub fn uncheck(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    actor_id: &str,
+  ) -> Result<Todo, result::Error> {
+    self.check_as(connection, actor_id, false)
+  }
+
+  /// Replace the content of the todo with the new one
+  pub fn update_content(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    actor_id: &str,
+    content: &str,
+  ) -> Result<Todo, result::Error> {
+    connection.transaction(|| {
+      let target = todos::table.filter(todos::id.eq(&self.id));
+      let updated = diesel::update(target)
+        .set(todos::content.eq(content))
+        .get_result::<Todo>(connection)?;
+
+      NewTodoEvent::new(&self.id, Some(actor_id), EventKind::Update)
+        .change(
+          Some(json!({ "content": self.content })),
+          Some(json!({ "content": updated.content })),
+        )
+        .record(connection)?;
+
+      Ok(updated)
+    })
+  }
+
+  /// End the series of the recurring todo, none of its occurrences recur
+  /// anymore so no new ones get created. Existing occurrences are kept.
+  pub fn stop_recurrence(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    actor_id: &str,
+  ) -> Result<Todo, result::Error> {
+    connection.transaction(|| {
+      let series_id = self.series_id.clone().unwrap_or_else(|| self.id.clone());
+      let target =
+        todos::table.filter(todos::series_id.eq(&series_id).or(todos::id.eq(&self.id)));
+      diesel::update(target)
+        .set(todos::recurrence.eq(None::<String>))
+        .execute(connection)?;
+
+      NewTodoEvent::new(&self.id, Some(actor_id), EventKind::Update)
+        .change(
+          Some(json!({ "recurrence": self.recurrence })),
+          Some(json!({ "recurrence": null })),
+        )
+        .record(connection)?;
+
+      todos::table.find(&self.id).first::<Todo>(connection)
+    })
+  }
+
+  /// Move the todo together with all of its subtasks to the trash
+  pub fn delete(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    actor_id: &str,
+  ) -> Result<bool, result::Error> {
+    connection.transaction(|| {
+      let mut ids = self.descendant_ids(connection)?;
+      ids.push(self.id.clone());
+
+      let target = todos::table
+        .filter(todos::id.eq_any(ids))
+        .filter(todos::deleted_at.is_null());
+      let deleted = diesel::update(target)
+        .set(todos::deleted_at.eq(Some(Utc::now())))
+        .get_results::<Todo>(connection)?;
+
+      for todo in &deleted {
+        NewTodoEvent::new(&todo.id, Some(actor_id), EventKind::Delete).record(connection)?;
+      }
+
+      if deleted.is_empty() {
+        Err(result::Error::NotFound)
+      } else {
+        Ok(true)
+      }
+    })
+  }
+
+  /// Take the todo out of the trash, together with the subtasks that were
+  /// trashed along with it. Subtask whose parent is still in the trash is
+  /// detached from it, so it is listed among the other todos.
+  pub fn restore(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    actor_id: &str,
+  ) -> Result<Todo, result::Error> {
+    connection.transaction(|| {
+      if let Some(parent_id) = &self.parent_id {
+        let parent_trashed = todos::table
+          .filter(todos::id.eq(parent_id))
+          .filter(todos::deleted_at.is_not_null())
+          .count()
+          .get_result::<i64>(connection)?
+          > 0;
+
+        if parent_trashed {
+          diesel::update(todos::table.filter(todos::id.eq(&self.id)))
+            .set(todos::parent_id.eq(None::<String>))
+            .execute(connection)?;
+        }
+      }
+
+      let subtasks = todos::table
+        .filter(todos::id.eq_any(self.descendant_ids(connection)?))
+        .filter(todos::deleted_at.eq(self.deleted_at));
+      let subtasks = diesel::update(subtasks)
+        .set(todos::deleted_at.eq(None::<DateTime<Utc>>))
+        .get_results::<Todo>(connection)?;
+
+      let target = todos::table.filter(todos::id.eq(&self.id));
+      let restored = diesel::update(target)
+        .set(todos::deleted_at.eq(None::<DateTime<Utc>>))
+        .get_result::<Todo>(connection)?;
+
+      for todo in subtasks.iter().chain(std::iter::once(&restored)) {
+        NewTodoEvent::new(&todo.id, Some(actor_id), EventKind::Restore).record(connection)?;
+      }
+
+      Ok(restored)
+    })
+  }
+
+  /// Assign the todo to the user, or leave it unassigned with none
+  pub fn assign(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    actor_id: &str,
+    assignee_id: Option<&str>,
+  ) -> Result<Todo, result::Error> {
+    connection.transaction(|| {
+      let target = todos::table.filter(todos::id.eq(&self.id));
+      let assigned = diesel::update(target)
+        .set(todos::assignee_id.eq(assignee_id))
+        .get_result::<Todo>(connection)?;
+
+      NewTodoEvent::new(&self.id, Some(actor_id), EventKind::Assign)
+        .change(
+          Some(json!({ "assignee_id": self.assignee_id })),
+          Some(json!({ "assignee_id": assigned.assignee_id })),
+        )
+        .record(connection)?;
+
+      Ok(assigned)
+    })
+  }
+
+  /// Put the todo together with all of its subtasks on the given list,
+  /// or take them off the list with none
+  pub fn move_to_list(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    actor_id: &str,
+    list_id: Option<&str>,
+  ) -> Result<Todo, result::Error> {
+    connection.transaction(|| {
+      // Subtasks go along, so they are shared with the same users as their parent
+      let subtasks = todos::table
+        .filter(todos::id.eq_any(self.descendant_ids(connection)?))
+        .load::<Todo>(connection)?;
+      let ids: Vec<&String> = subtasks.iter().map(|subtask| &subtask.id).collect();
+      diesel::update(todos::table.filter(todos::id.eq_any(ids)))
+        .set(todos::list_id.eq(list_id))
+        .execute(connection)?;
+
+      let target = todos::table.filter(todos::id.eq(&self.id));
+      let moved = diesel::update(target)
+        .set(todos::list_id.eq(list_id))
+        .get_result::<Todo>(connection)?;
+
+      for todo in subtasks.iter().chain(std::iter::once(self)) {
+        NewTodoEvent::new(&todo.id, Some(actor_id), EventKind::Move)
+          .change(
+            Some(json!({ "list_id": todo.list_id })),
+            Some(json!({ "list_id": moved.list_id })),
+          )
+          .record(connection)?;
+      }
+
+      Ok(moved)
+    })
+  }
+
+  /// Move the todo so it is placed after and/or before the todos with the
+  /// given ids. Only the moved todo gets a new position, unless there is
+  /// no more room between its neighbours and the list has to be renumbered.
+  /// None is returned when both neighbours are given but they are not next
+  /// to each other anymore.
+  pub fn move_between(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    actor_id: &str,
+    after: Option<&str>,
+    before: Option<&str>,
+  ) -> Result<Option<Todo>, result::Error> {
+    connection.transaction(|| {
+      if let (Some(after), Some(before)) = (after, before) {
+        if !self.are_adjacent(connection, after, before)? {
+          return Ok(None);
+        }
+      }
+
+      let position = match self.position_for(connection, after, before)? {
+        Some(position) => position,
+        None => {
+          self.renumber(connection)?;
+          match self.position_for(connection, after, before)? {
+            Some(position) => position,
+            None => return Err(result::Error::RollbackTransaction),
+          }
+        }
+      };
+
+      let target = todos::table.filter(todos::id.eq(&self.id));
+      let moved = diesel::update(target)
+        .set(todos::position.eq(position))
+        .get_result::<Todo>(connection)?;
+
+      NewTodoEvent::new(&self.id, Some(actor_id), EventKind::Move)
+        .change(
+          Some(json!({ "position": self.position })),
+          Some(json!({ "position": moved.position })),
+        )
+        .record(connection)?;
+
+      Ok(Some(moved))
+    })
+  }
+
+  /// Todos that are ordered together with this one, see `position_scope`
+  fn position_scope(&self) -> Box<dyn BoxableExpression<todos::table, Pg, SqlType = Bool>> {
+    position_scope(&self.user_id, self.list_id.as_deref())
+  }
+
+  /// Check that the first todo comes right before the second one, with no
+  /// other todo in between them
+  fn are_adjacent(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    first: &str,
+    second: &str,
+  ) -> Result<bool, result::Error> {
+    let position_of = |id: &str| {
+      todos::table
+        .filter(self.position_scope())
+        .filter(todos::id.eq(id))
+        .filter(todos::deleted_at.is_null())
+        .select(todos::position)
+        .for_update()
+        .first::<f64>(connection)
+    };
+
+    let lower = position_of(first)?;
+    let upper = position_of(second)?;
+
+    if lower >= upper {
+      return Ok(false);
+    }
+
+    let between: i64 = todos::table
+      .filter(self.position_scope())
+      .filter(todos::id.ne(&self.id))
+      .filter(todos::deleted_at.is_null())
+      .filter(todos::position.gt(lower))
+      .filter(todos::position.lt(upper))
+      .count()
+      .get_result(connection)?;
+
+    Ok(between == 0)
+  }
+
+  /// Find the position between the neighbours, when only one of them is
+  /// given the other one is the next todo in the list on that side.
+  fn position_for(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    after: Option<&str>,
+    before: Option<&str>,
+  ) -> Result<Option<f64>, result::Error> {
+    let position_of = |id: &str| {
+      todos::table
+        .filter(self.position_scope())
+        .filter(todos::id.eq(id))
+        .filter(todos::deleted_at.is_null())
+        .select(todos::position)
+        .first::<f64>(connection)
+    };
+
+    let lower = match after {
+      Some(id) => Some(position_of(id)?),
+      None => None,
+    };
+
+    let upper = match before {
+      Some(id) => Some(position_of(id)?),
+      None => None,
+    };
+
+    let others = || {
+      todos::table
+        .filter(self.position_scope())
+        .filter(todos::id.ne(&self.id))
+        .filter(todos::deleted_at.is_null())
+    };
+
+    let (lower, upper) = match (lower, upper) {
+      (Some(lower), None) => (
+        Some(lower),
+        others()
+          .filter(todos::position.gt(lower))
+          .select(min(todos::position))
+          .first::<Option<f64>>(connection)?,
+      ),
+      (None, Some(upper)) => (
+        others()
+          .filter(todos::position.lt(upper))
+          .select(max(todos::position))
+          .first::<Option<f64>>(connection)?,
+        Some(upper),
+      ),
+      bounds => bounds,
+    };
+
+    Ok(position_between(lower, upper))
+  }
+
+  /// Give the todos ordered together with this one whole number positions
+  /// keeping their order
+  fn renumber(&self, connection: &crate::diesel::PgConnection) -> Result<(), result::Error> {
+    let ids = todos::table
+      .filter(self.position_scope())
+      .filter(todos::deleted_at.is_null())
+      .order((todos::position.asc(), todos::id.asc()))
+      .select(todos::id)
+      .load::<String>(connection)?;
+
+    for (index, id) in ids.iter().enumerate() {
+      diesel::update(todos::table.filter(todos::id.eq(id)))
+        .set(todos::position.eq((index + 1) as f64))
+        .execute(connection)?;
     }
+
+    Ok(())
+  }
+
+  /// Check or uncheck the Todo, completion time is set when checked and
+  /// cleared when unchecked.
+  fn check_as(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    actor_id: &str,
+    value: bool,
+  ) -> Result<Todo, result::Error> {
+    connection.transaction(|| {
+      let completed_at = if value { Some(Utc::now()) } else { None };
+      let target = todos::table.filter(todos::id.eq(&self.id));
+
+      let updated = diesel::update(target)
+        .set((
+          todos::checked.eq(value),
+          todos::completed_at.eq(completed_at),
+        ))
+        .get_result::<Todo>(connection)?;
+
+      let kind = if value {
+        EventKind::Check
+      } else {
+        EventKind::Uncheck
+      };
+      NewTodoEvent::new(&self.id, Some(actor_id), kind)
+        .change(
+          Some(json!({ "checked": self.checked })),
+          Some(json!({ "checked": updated.checked })),
+        )
+        .record(connection)?;
+
+      Ok(updated)
+    })
+  }
+}
+
+/// Todos on a list are ordered together whoever created them, the todos
+/// that are not on any list are ordered per user.
+fn position_scope(
+  user_id: &str,
+  list_id: Option<&str>,
+) -> Box<dyn BoxableExpression<todos::table, Pg, SqlType = Bool>> {
+  match list_id {
+    Some(list_id) => Box::new(todos::list_id.eq(String::from(list_id))),
+    None => Box::new(
+      todos::user_id
+        .eq(String::from(user_id))
+        .and(todos::list_id.is_null()),
+    ),
+  }
+}
+
+/// Position between the two neighbouring positions, none is returned when
+/// there is no room left between them.
+fn position_between(lower: Option<f64>, upper: Option<f64>) -> Option<f64> {
+  let position = match (lower, upper) {
+    (Some(lower), Some(upper)) => lower + (upper - lower) / 2.0,
+    (Some(lower), None) => lower + 1.0,
+    (None, Some(upper)) => upper - 1.0,
+    (None, None) => 1.0,
+  };
+
+  let above_lower = match lower {
+    Some(lower) => position > lower,
+    None => true,
+  };
+  let below_upper = match upper {
+    Some(upper) => position < upper,
+    None => true,
+  };
+
+  if above_lower && below_upper {
+    Some(position)
+  } else {
+    None
   }
 }
 
@@ -116,22 +1429,142 @@ impl Todo {
 pub struct NewTodo {
   pub user_id: String,
   pub content: String,
+  pub due_at: Option<DateTime<Utc>>,
+  pub priority: Priority,
+  pub list_id: Option<String>,
+  pub parent_id: Option<String>,
+  pub recurrence: Option<String>,
+  pub series_id: Option<String>,
 }
 
 impl NewTodo {
+  /// Prepare new todo for the user, optional attributes can be set
+  /// on the returned struct before inserting it.
+  pub fn new(user_id: &str, content: &str) -> Self {
+    Self {
+      user_id: String::from(user_id),
+      content: String::from(content),
+      due_at: None,
+      priority: Priority::default(),
+      list_id: None,
+      parent_id: None,
+      recurrence: None,
+      series_id: None,
+    }
+  }
+
   /// Create new todo with given parameters.
   pub fn create<'a>(
     connection: &crate::diesel::PgConnection,
     user_id: &'a str,
     content: &'a str,
   ) -> Result<Todo, result::Error> {
-    let values = Self {
-      content: String::from(content),
-      user_id: String::from(user_id),
+    Self::new(user_id, content).insert(connection)
+  }
+
+  /// Insert the prepared todo into the database, it is placed at the end
+  /// of its list or of the user todos.
+  pub fn insert(&self, connection: &crate::diesel::PgConnection) -> Result<Todo, result::Error> {
+    connection.transaction(|| {
+      let todo = diesel::insert_into(todos::table)
+        .values((self, todos::position.eq(self.next_position(connection)?)))
+        .get_result::<Todo>(connection)?;
+
+      NewTodoEvent::new(&todo.id, Some(&self.user_id), EventKind::Create)
+        .change(None, Some(json!({ "content": todo.content })))
+        .record(connection)?;
+
+      // First occurrence of the recurring todo starts a new series
+      if todo.recurrence.is_some() && todo.series_id.is_none() {
+        let target = todos::table.filter(todos::id.eq(&todo.id));
+        return diesel::update(target)
+          .set(todos::series_id.eq(&todo.id))
+          .get_result::<Todo>(connection);
+      }
+
+      Ok(todo)
+    })
+  }
+
+  /// Insert the occurrence of the recurring todo, none is returned when its
+  /// series already has an occurrence that is due at the same time.
+  pub fn insert_occurrence(
+    &self,
+    connection: &crate::diesel::PgConnection,
+  ) -> Result<Option<Todo>, result::Error> {
+    connection.transaction(|| {
+      let todo = diesel::insert_into(todos::table)
+        .values((self, todos::position.eq(self.next_position(connection)?)))
+        .on_conflict((todos::series_id, todos::due_at))
+        .do_nothing()
+        .get_result::<Todo>(connection)
+        .optional()?;
+
+      // Occurrences are created by the application, there is no actor
+      if let Some(todo) = &todo {
+        NewTodoEvent::new(&todo.id, None, EventKind::Create)
+          .change(None, Some(json!({ "content": todo.content })))
+          .record(connection)?;
+      }
+
+      Ok(todo)
+    })
+  }
+
+  /// Position right after the last todo of the list, or of the user when
+  /// the todo is not on any list
+  fn next_position(&self, connection: &crate::diesel::PgConnection) -> Result<f64, result::Error> {
+    let last = todos::table
+      .filter(position_scope(&self.user_id, self.list_id.as_deref()))
+      .select(max(todos::position))
+      .first::<Option<f64>>(connection)?;
+
+    Ok(last.unwrap_or(0.0) + 1.0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{position_between, CursorKey, SortField, TodoCursor, TodoSort};
+  #[test]
+  fn parse_todo_sort() {
+    let sort: TodoSort = "-priority".parse().unwrap();
+    assert_eq!(sort.field, SortField::Priority);
+    assert!(sort.descending);
+
+    let sort: TodoSort = "due_at".parse().unwrap();
+    assert_eq!(sort.field, SortField::DueAt);
+    assert!(!sort.descending);
+
+    assert!("-unknown".parse::<TodoSort>().is_err());
+  }
+
+  #[test]
+  fn find_position_between_neighbours() {
+    assert_eq!(position_between(Some(1.0), Some(2.0)), Some(1.5));
+    assert_eq!(position_between(Some(3.0), None), Some(4.0));
+    assert_eq!(position_between(None, Some(3.0)), Some(2.0));
+    assert_eq!(position_between(None, None), Some(1.0));
+    assert_eq!(position_between(Some(2.0), Some(1.0)), None);
+
+    let lower = 1.0;
+    let upper = lower + f64::EPSILON;
+    assert_eq!(position_between(Some(lower), Some(upper)), None);
+  }
+
+  #[test]
+  fn decode_encoded_cursor() {
+    let cursor = TodoCursor {
+      page: 2,
+      key: CursorKey::DueAt(None),
+      descending: true,
+      id: "abc".into(),
     };
 
-    diesel::insert_into(todos::table)
-      .values(&values)
-      .get_result::<Todo>(connection)
+    let decoded = TodoCursor::decode(&cursor.encode()).unwrap();
+    assert_eq!(decoded, cursor);
+    assert_eq!(decoded.sort(), "-due_at".parse().unwrap());
+
+    assert!(TodoCursor::decode("not a cursor").is_err());
   }
 }
@@ -0,0 +1,159 @@
+use super::super::schema::todo_events;
+use super::Paginated;
+use crate::diesel::ExpressionMethods;
+use crate::diesel::QueryDsl;
+use crate::diesel::RunQueryDsl;
+use chrono::{DateTime, Utc};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::Pg;
+use diesel::result;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use serde_json::Value;
+use std::io::Write;
+
+/// Kind of the change recorded in the history of the todo
+#[derive(AsExpression, FromSqlRow, Clone, Copy, PartialEq, Debug, serde::Serialize)]
+#[sql_type = "Text"]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+  Create,
+  Update,
+  Check,
+  Uncheck,
+  Delete,
+  Restore,
+  Move,
+  Assign,
+  CommentAdded,
+  CommentEdited,
+  CommentDeleted,
+}
+
+impl EventKind {
+  fn as_str(&self) -> &'static str {
+    match self {
+      EventKind::Create => "create",
+      EventKind::Update => "update",
+      EventKind::Check => "check",
+      EventKind::Uncheck => "uncheck",
+      EventKind::Delete => "delete",
+      EventKind::Restore => "restore",
+      EventKind::Move => "move",
+      EventKind::Assign => "assign",
+      EventKind::CommentAdded => "comment_added",
+      EventKind::CommentEdited => "comment_edited",
+      EventKind::CommentDeleted => "comment_deleted",
+    }
+  }
+}
+
+impl ToSql<Text, Pg> for EventKind {
+  fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+    ToSql::<Text, Pg>::to_sql(self.as_str(), out)
+  }
+}
+
+impl FromSql<Text, Pg> for EventKind {
+  fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+    match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+      "create" => Ok(EventKind::Create),
+      "update" => Ok(EventKind::Update),
+      "check" => Ok(EventKind::Check),
+      "uncheck" => Ok(EventKind::Uncheck),
+      "delete" => Ok(EventKind::Delete),
+      "restore" => Ok(EventKind::Restore),
+      "move" => Ok(EventKind::Move),
+      "assign" => Ok(EventKind::Assign),
+      "comment_added" => Ok(EventKind::CommentAdded),
+      "comment_edited" => Ok(EventKind::CommentEdited),
+      "comment_deleted" => Ok(EventKind::CommentDeleted),
+      value => Err(format!("Unknown event kind: {}", value).into()),
+    }
+  }
+}
+
+/// Single change in the history of the todo, the actor is missing for the
+/// changes made by the application itself, e.g. scheduled occurrences.
+#[derive(Queryable, PartialEq, Debug, serde::Serialize)]
+pub struct TodoEvent {
+  pub id: String,
+  pub todo_id: String,
+  pub actor_id: Option<String>,
+  pub kind: EventKind,
+  pub old_value: Option<Value>,
+  pub new_value: Option<Value>,
+  pub created_at: DateTime<Utc>,
+}
+
+impl TodoEvent {
+  /// Get paginated history of the todo, the oldest events come first
+  pub fn history(
+    connection: &crate::diesel::PgConnection,
+    page: u32,
+    per_page: u32,
+    todo_id: &str,
+  ) -> Result<Paginated<TodoEvent>, result::Error> {
+    let mut data: Vec<TodoEvent> = vec![];
+
+    let total: i64 = todo_events::table
+      .filter(todo_events::todo_id.eq(todo_id))
+      .count()
+      .get_result(connection)?;
+
+    if total > 0 {
+      let skip = (page as i64 - 1) * per_page as i64;
+
+      data = todo_events::table
+        .filter(todo_events::todo_id.eq(todo_id))
+        .order((todo_events::created_at.asc(), todo_events::id.asc()))
+        .offset(skip)
+        .limit(per_page as i64)
+        .load::<TodoEvent>(connection)?;
+    }
+
+    Ok(Paginated::new(page, per_page, total as u32, data))
+  }
+}
+
+#[derive(Insertable)]
+#[table_name = "todo_events"]
+pub struct NewTodoEvent {
+  pub todo_id: String,
+  pub actor_id: Option<String>,
+  pub kind: EventKind,
+  pub old_value: Option<Value>,
+  pub new_value: Option<Value>,
+  pub created_at: DateTime<Utc>,
+}
+
+impl NewTodoEvent {
+  /// Prepare the event of the todo, values of the change can be attached
+  /// with `change` before recording it.
+  pub fn new(todo_id: &str, actor_id: Option<&str>, kind: EventKind) -> Self {
+    Self {
+      todo_id: String::from(todo_id),
+      actor_id: actor_id.map(String::from),
+      kind,
+      old_value: None,
+      new_value: None,
+      created_at: Utc::now(),
+    }
+  }
+
+  /// Attach the values from before and after the change
+  pub fn change(mut self, old_value: Option<Value>, new_value: Option<Value>) -> Self {
+    self.old_value = old_value;
+    self.new_value = new_value;
+    self
+  }
+
+  /// Record the event, should run in the same transaction as the change
+  pub fn record(&self, connection: &crate::diesel::PgConnection) -> Result<(), result::Error> {
+    diesel::insert_into(todo_events::table)
+      .values(self)
+      .execute(connection)?;
+
+    Ok(())
+  }
+}
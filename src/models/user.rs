@@ -2,11 +2,13 @@ use crate::diesel::ExpressionMethods;
 use crate::diesel::QueryDsl;
 use crate::diesel::RunQueryDsl;
 use crate::models;
+use crate::models::refresh_token::RefreshToken;
 use crate::schema::users;
-use crate::virtual_schema::users_todos;
 use bcrypt;
+use chrono::{DateTime, Utc};
 use diesel::result;
-use diesel::sql_query;
+use diesel::Connection;
+use diesel::OptionalExtension;
 use serde::ser::SerializeStruct;
 use uuid::Uuid;
 
@@ -18,6 +20,8 @@ pub struct User {
   pub id: String,
   pub email: String,
   pub password: String,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
 }
 
 impl serde::Serialize for User {
@@ -25,9 +29,11 @@ impl serde::Serialize for User {
   where
     S: serde::Serializer,
   {
-    let mut s = serializer.serialize_struct("User", 3)?;
+    let mut s = serializer.serialize_struct("User", 4)?;
     s.serialize_field("id", &self.id)?;
     s.serialize_field("email", &self.email)?;
+    s.serialize_field("created_at", &self.created_at)?;
+    s.serialize_field("updated_at", &self.updated_at)?;
     s.end()
   }
 }
@@ -35,10 +41,14 @@ impl serde::Serialize for User {
 impl User {
   /// Create new user
   pub fn new(email: String, hashed_password: String) -> User {
+    let now = Utc::now();
+
     User {
       id: Uuid::new_v4().to_string(),
       email,
       password: hashed_password,
+      created_at: now,
+      updated_at: now,
     }
   }
 
@@ -47,6 +57,14 @@ impl User {
     users::table.load::<Self>(connection)
   }
 
+  /// Find single user by its id
+  pub fn find(
+    connection: &crate::diesel::PgConnection,
+    id: &str,
+  ) -> Result<Option<User>, result::Error> {
+    users::table.find(id).first::<User>(connection).optional()
+  }
+
   /// Find single user by its email
   pub fn find_by_email(
     connection: &crate::diesel::PgConnection,
@@ -70,65 +88,70 @@ impl User {
     models::todo::NewTodo::create(connection, &self.id, &todo_content)
   }
 
-  /// Generate authentication JWT token
-  pub fn generate_jwt(&self) -> String {
-    crate::services::jwt::generate(&self)
+  /// Replace the password of the user, password will be automatically hashed into bcrypt.
+  pub fn update_password(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    password: &str,
+  ) -> Result<User, result::Error> {
+    diesel::update(users::table.find(&self.id))
+      .set(users::password.eq(hash_password(password)?))
+      .get_result::<User>(connection)
   }
 
-  /// Convert decoded claims from JWT token into an User object
-  pub fn from_jwt(claims: &crate::services::jwt::Claims) -> Self {
-    User {
-      id: String::from(&claims.sub),
-      email: String::from(&claims.email),
-      password: String::new(),
-    }
+  /// Replace the password of the user and revoke all of the user's sessions,
+  /// except the one with given jti that is used to change the password
+  pub fn change_password(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    password: &str,
+    current_jti: Option<&str>,
+  ) -> Result<User, result::Error> {
+    connection.transaction(|| {
+      let user = self.update_password(connection, password)?;
+
+      match current_jti {
+        Some(jti) => RefreshToken::revoke_others(connection, &self.id, jti)?,
+        None => RefreshToken::revoke_all(connection, &self.id)?,
+      };
+
+      Ok(user)
+    })
+  }
+
+  /// Generate authentication JWT token with given token id
+  pub fn generate_jwt(
+    &self,
+    jwt: &crate::services::jwt::JwtConfig,
+    jti: &str,
+  ) -> Result<String, jsonwebtoken::errors::Error> {
+    jwt.generate(self, jti)
   }
 }
 
 /// User model that implements method to retrieve user with his todos together
-#[derive(Queryable, PartialEq, Debug, serde::Serialize)]
+#[derive(PartialEq, Debug, serde::Serialize)]
 pub struct UserWithTodo {
   pub id: String,
   pub email: String,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
   pub todos: Vec<models::todo::Todo>,
 }
 
-/// Temporary struct for incoming data from the join sql query
-#[derive(QueryableByName, PartialEq, Debug)]
-#[table_name = "users_todos"]
-struct TempUserWithTodo {
-  id: String,
-  email: String,
-  content: String,
-  checked: bool,
-  user_id: String,
-}
-
 impl UserWithTodo {
   /// Get user struct with todos included
   pub fn show(connection: &crate::diesel::PgConnection, id: &str) -> Result<Self, result::Error> {
-    let results = sql_query(format!(include_str!("../../sql/user_with_todos.sql"), id))
-      .load::<TempUserWithTodo>(connection)?;
-
-    let mut user = match results.first() {
-      Some(item) => UserWithTodo {
-        id: String::from(&item.user_id),
-        email: String::from(&item.email),
-        todos: vec![],
-      },
-      _ => return Err(result::Error::NotFound),
-    };
-
-    for todo in results.iter() {
-      user.todos.push(models::todo::Todo {
-        id: String::from(&todo.id),
-        user_id: String::from(&todo.user_id),
-        content: String::from(&todo.content),
-        checked: todo.checked,
-      });
-    }
-
-    Ok(user)
+    let user = users::table.find(id).first::<User>(connection)?;
+    let todos = models::todo::Todo::users(connection, &user.id)?;
+
+    Ok(UserWithTodo {
+      id: user.id,
+      email: user.email,
+      created_at: user.created_at,
+      updated_at: user.updated_at,
+      todos,
+    })
   }
 }
 
@@ -149,13 +172,7 @@ impl NewUser {
     email: &'a str,
     password: &'a str,
   ) -> Result<User, result::Error> {
-    let hashed_password = match bcrypt::hash(&password, bcrypt::DEFAULT_COST) {
-      Ok(hashed) => hashed,
-      Err(e) => {
-        println!("Hashing password error: {:?}", e);
-        return Err(result::Error::__Nonexhaustive);
-      }
-    };
+    let hashed_password = hash_password(password)?;
 
     let values = Self {
       email: String::from(email),
@@ -167,3 +184,14 @@ impl NewUser {
       .get_result::<User>(connection)
   }
 }
+
+/// Hash the password into bcrypt
+fn hash_password(password: &str) -> Result<String, result::Error> {
+  match bcrypt::hash(password, bcrypt::DEFAULT_COST) {
+    Ok(hashed) => Ok(hashed),
+    Err(e) => {
+      println!("Hashing password error: {:?}", e);
+      Err(result::Error::__Nonexhaustive)
+    }
+  }
+}
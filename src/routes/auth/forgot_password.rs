@@ -0,0 +1,49 @@
+use crate::models::password_reset::NewPasswordReset;
+use crate::models::user::User;
+use crate::services::mailer::Mail;
+use crate::state::app::AppState;
+use crate::validation::forgot_password_request::ForgotPasswordRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Send the token for resetting the password to the user with given email.
+/// Response is the same whether the user exists or not, so it can not be
+/// used to find out who is registered.
+///
+/// @param {String} email
+///
+/// Success code 204
+///
+/// Error: 400
+pub async fn handle(
+  data: Json<ForgotPasswordRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let connection = &state.get_connection();
+
+  let user = match User::find_by_email(connection, &data.email) {
+    Ok(Some(user)) => user,
+    Ok(None) => return HttpResponse::NoContent().finish(),
+    Err(_) => return HttpResponse::BadRequest().finish(),
+  };
+
+  let token = match NewPasswordReset::create(connection, &user.id) {
+    Ok(token) => token,
+    Err(_) => return HttpResponse::BadRequest().finish(),
+  };
+
+  let mail = Mail {
+    to: user.email,
+    subject: String::from("Reset your password"),
+    body: format!(
+      "Use the following token to set the new password, it can be used only once:\n{}",
+      token
+    ),
+  };
+
+  if let Err(e) = state.mailer().send(&mail) {
+    println!("Password reset: Could not send the mail to {}: {:?}", mail.to, e);
+  }
+
+  HttpResponse::NoContent().finish()
+}
@@ -2,7 +2,8 @@ use crate::models::auth::AuthenticableUser;
 use crate::state::app::AppState;
 use actix_web::{web, HttpResponse, Responder};
 
-/// Authenticate the user with email and password
+/// Authenticate the user with email and password, the access token is
+/// returned in the `jwt` header and the refresh token in the `refresh-token` header
 ///
 /// @param {String} email
 /// @param {String} password
@@ -11,17 +12,22 @@ use actix_web::{web, HttpResponse, Responder};
 /// ```
 /// {
 ///   "id": "be24fb8b-09ca-472c-abef-4ae04c530cfd",
-///   "email": "test@barrage.net"
+///   "email": "test@barrage.net",
+///   "created_at": "2020-10-19T11:00:00Z",
+///   "updated_at": "2020-10-19T11:00:00Z"
 /// }
 /// ```
 ///
-/// Error: 400 or 401
+/// Error: 400, 401 or 500
 pub async fn handle(
   user: web::Json<AuthenticableUser>,
   state: web::Data<AppState>,
 ) -> impl Responder {
-  match AuthenticableUser::authenticate(&state.get_connection(), &user.email, &user.password) {
-    Ok((authenticated, token)) => HttpResponse::Ok().header("jwt", token).json(authenticated),
-    Err(_) => HttpResponse::Unauthorized().finish(),
-  }
+  let authenticated =
+    match AuthenticableUser::authenticate(&state.get_connection(), &user.email, &user.password) {
+      Ok(authenticated) => authenticated,
+      Err(_) => return HttpResponse::Unauthorized().finish(),
+    };
+
+  super::tokens_response(&state, authenticated)
 }
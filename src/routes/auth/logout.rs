@@ -0,0 +1,23 @@
+use crate::middleware::auth::TokenId;
+use crate::models::refresh_token::RefreshToken;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Revoke the access token that the request was made with, together
+/// with its refresh token
+///
+/// Success code 204
+///
+/// Error: 400
+pub async fn handle(req: web::HttpRequest, state: web::Data<AppState>) -> impl Responder {
+  // Only requests authenticated with the token have something to revoke
+  let token_id = match req.extensions_mut().remove::<TokenId>() {
+    Some(token_id) => token_id,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  match RefreshToken::revoke(&state.get_connection(), &token_id.0) {
+    Ok(_) => HttpResponse::NoContent().finish(),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -1,2 +1,34 @@
+pub mod forgot_password;
 pub mod login;
+pub mod logout;
+pub mod refresh;
 pub mod register;
+pub mod reset_password;
+
+use crate::models::refresh_token::{IssueError, NewRefreshToken};
+use crate::models::user::User;
+use crate::state::app::AppState;
+use actix_web::HttpResponse;
+
+/// Respond with the user, issuing the new pair of tokens for it in the headers
+pub fn tokens_response(state: &AppState, user: User) -> HttpResponse {
+  match NewRefreshToken::issue(&state.get_connection(), state.jwt(), &user) {
+    Ok(tokens) => HttpResponse::Ok()
+      .header("jwt", tokens.access_token)
+      .header("refresh-token", tokens.refresh_token)
+      .json(user),
+    Err(e) => issue_error_response(e),
+  }
+}
+
+/// Respond to the tokens that could not be issued, failing to sign
+/// the token is a problem of the server
+pub fn issue_error_response(error: IssueError) -> HttpResponse {
+  match error {
+    IssueError::Jwt(e) => {
+      println!("Authentication: Could not sign the token: {:?}", e);
+      HttpResponse::InternalServerError().finish()
+    }
+    IssueError::Database(_) => HttpResponse::BadRequest().finish(),
+  }
+}
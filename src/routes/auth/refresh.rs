@@ -0,0 +1,33 @@
+use crate::models::refresh_token::RefreshToken;
+use crate::state::app::AppState;
+use crate::validation::refresh_token_request::RefreshTokenRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Exchange the refresh token for the new access token and refresh token,
+/// returned in the `jwt` and `refresh-token` headers. Each refresh token
+/// can be used only once.
+///
+/// @param {String} refresh_token
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "be24fb8b-09ca-472c-abef-4ae04c530cfd",
+///   "email": "test@barrage.net",
+///   "created_at": "2020-10-19T11:00:00Z",
+///   "updated_at": "2020-10-19T11:00:00Z"
+/// }
+/// ```
+///
+/// Error: 400, 401 or 500
+pub async fn handle(data: Json<RefreshTokenRequest>, state: web::Data<AppState>) -> impl Responder {
+  match RefreshToken::rotate(&state.get_connection(), state.jwt(), &data.refresh_token) {
+    Ok(Some((user, tokens))) => HttpResponse::Ok()
+      .header("jwt", tokens.access_token)
+      .header("refresh-token", tokens.refresh_token)
+      .json(user),
+    Ok(None) => HttpResponse::Unauthorized().finish(),
+    Err(e) => super::issue_error_response(e),
+  }
+}
@@ -13,7 +13,9 @@ use actix_web_validator::Json;
 /// ```
 /// {
 ///   "id": "be24fb8b-09ca-472c-abef-4ae04c530cfd",
-///   "email": "test@barrage.net"
+///   "email": "test@barrage.net",
+///   "created_at": "2020-10-19T11:00:00Z",
+///   "updated_at": "2020-10-19T11:00:00Z"
 /// }
 /// ```
 ///
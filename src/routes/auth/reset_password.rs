@@ -0,0 +1,27 @@
+use crate::models::password_reset::PasswordReset;
+use crate::state::app::AppState;
+use crate::validation::reset_password_request::ResetPasswordRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Set the new password with the token that was sent to the user, all the
+/// sessions of the user are logged out
+///
+/// @param {String} token
+/// @param {String} password
+///
+/// Success code 204
+///
+/// Error: 400
+pub async fn handle(
+  data: Json<ResetPasswordRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  match PasswordReset::reset(&state.get_connection(), &data.token, &data.password) {
+    Ok(Some(user)) => {
+      state.users().forget(&user.id);
+      HttpResponse::NoContent().finish()
+    }
+    Ok(None) | Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
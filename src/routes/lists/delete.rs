@@ -0,0 +1,34 @@
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Delete the list, todos on the list are kept without it
+///
+/// @param {String} list_id
+///
+/// Success code 204
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let list = match super::find_authorized(connection, &path.0, &auth, Access::Manage) {
+    Ok(list) => list,
+    Err(response) => return response,
+  };
+
+  match list.delete(connection) {
+    Ok(_) => HttpResponse::NoContent().finish(),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
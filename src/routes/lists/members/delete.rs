@@ -0,0 +1,44 @@
+use crate::models::list_member::ListMember;
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Stop sharing the list with the user, members can also leave the list
+/// on their own
+///
+/// @param {String} list_id
+/// @param {String} user_id
+///
+/// Success code 204
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<(String, String)>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let (list_id, user_id) = path.into_inner();
+  let connection = &state.get_connection();
+
+  let access = if user_id == auth.id {
+    Access::View
+  } else {
+    Access::Manage
+  };
+
+  let list = match super::super::find_authorized(connection, &list_id, &auth, access) {
+    Ok(list) => list,
+    Err(response) => return response,
+  };
+
+  match ListMember::remove(connection, &list.id, &user_id) {
+    Ok(_) => HttpResponse::NoContent().finish(),
+    Err(_) => HttpResponse::NotFound().finish(),
+  }
+}
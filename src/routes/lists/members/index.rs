@@ -0,0 +1,45 @@
+use crate::models::list_member::ListMember;
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Get all the users that the list was shared with
+///
+/// @param {String} list_id
+///
+/// Success code 200:
+/// ```
+/// [
+///   {
+///     "user_id": "7e6d5c4b-3a29-4180-9f8e-7d6c5b4a3928",
+///     "email": "friend@example.com",
+///     "role": "editor",
+///     "created_at": "2020-10-20T09:30:00Z"
+///   }
+/// ]
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let list = match super::super::find_authorized(connection, &path.0, &auth, Access::View) {
+    Ok(list) => list,
+    Err(response) => return response,
+  };
+
+  match ListMember::for_list(connection, &list.id) {
+    Ok(members) => HttpResponse::Ok().json(members),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -0,0 +1,65 @@
+use crate::models::list_member::{Member, NewListMember};
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use crate::validation::member_request::MemberRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Share the list with the user that has given email, the role of the user
+/// is replaced when the list was already shared with them
+///
+/// @param {String} list_id
+/// @param {String} email
+/// @param {String} role one of viewer, editor or owner
+///
+/// Success code 200:
+/// ```
+/// {
+///   "user_id": "7e6d5c4b-3a29-4180-9f8e-7d6c5b4a3928",
+///   "email": "friend@example.com",
+///   "role": "editor",
+///   "created_at": "2020-10-20T09:30:00Z"
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  data: Json<MemberRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let list = match super::super::find_authorized(connection, &path.0, &auth, Access::Manage) {
+    Ok(list) => list,
+    Err(response) => return response,
+  };
+
+  let user = match User::find_by_email(connection, &data.email) {
+    Ok(Some(user)) => user,
+    Ok(None) => return HttpResponse::NotFound().finish(),
+    Err(_) => return HttpResponse::BadRequest().finish(),
+  };
+
+  // The user who created the list stays its owner
+  if user.id == list.user_id {
+    return HttpResponse::BadRequest().finish();
+  }
+
+  match NewListMember::save(connection, &list.id, &user.id, data.role) {
+    Ok(member) => HttpResponse::Ok().json(Member {
+      user_id: member.user_id,
+      email: user.email,
+      role: member.role,
+      created_at: member.created_at,
+    }),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
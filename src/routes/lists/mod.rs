@@ -0,0 +1,32 @@
+pub mod delete;
+pub mod index;
+pub mod members;
+pub mod show;
+pub mod store;
+pub mod todos;
+pub mod update;
+
+use crate::models::list::List;
+use crate::models::user::User;
+use crate::services::authorization::{self, Access};
+use actix_web::HttpResponse;
+
+/// Find the list with given id and make sure that the authenticated user
+/// is allowed the access on it. Error contains the response that should be returned.
+pub fn find_authorized(
+  connection: &crate::diesel::PgConnection,
+  list_id: &str,
+  auth: &User,
+  access: Access,
+) -> Result<List, HttpResponse> {
+  let list = match List::show(connection, list_id) {
+    Ok(list) => list,
+    Err(_) => return Err(HttpResponse::NotFound().finish()),
+  };
+
+  match authorization::can_access_list(connection, &list, &auth.id, access) {
+    Ok(true) => Ok(list),
+    Ok(false) => Err(HttpResponse::Forbidden().finish()),
+    Err(_) => Err(HttpResponse::BadRequest().finish()),
+  }
+}
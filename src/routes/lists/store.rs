@@ -0,0 +1,38 @@
+use crate::models::list::NewList;
+use crate::models::user::User;
+use crate::state::app::AppState;
+use crate::validation::list_request::ListRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Create new list
+///
+/// @param {String} name
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "5f0b7c1e-8d1a-4f4e-9d3b-2f7c9a6e4b10",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "name": "Groceries",
+///   "created_at": "2020-10-20T09:30:00Z",
+///   "updated_at": "2020-10-20T09:30:00Z"
+/// }
+/// ```
+///
+/// Error: 400
+pub async fn handle(
+  req: web::HttpRequest,
+  data: Json<ListRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  match NewList::create(&state.get_connection(), &auth.id, &data.name) {
+    Ok(list) => HttpResponse::Ok().json(list),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -0,0 +1,66 @@
+use crate::models::todo::Todo;
+use crate::models::user::User;
+use crate::routes::todos::index::PaginatedTodoRequest;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Get paginated todos on the list, accepts the same filters as `GET /todos`
+///
+/// @param {String} list_id
+///
+/// Success code 200:
+/// ```
+/// {
+///   "page": 1,
+///   "per_page": 10,
+///   "total": 12,
+///   "last_page": 1,
+///   "data": [ ... ]
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  query: web::Query<PaginatedTodoRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let list = match super::find_authorized(connection, &path.0, &auth, Access::View) {
+    Ok(list) => list,
+    Err(response) => return response,
+  };
+
+  let sort = match query.sort() {
+    Ok(sort) => sort,
+    Err(_) => return HttpResponse::BadRequest().finish(),
+  };
+
+  let page = match query.page(&sort) {
+    Ok(page) => page,
+    Err(_) => return HttpResponse::BadRequest().finish(),
+  };
+
+  let mut filter = query.filter(&auth.id);
+  filter.list_id = Some(list.id);
+
+  match Todo::paginated(
+    connection,
+    &page,
+    query.per_page(&state),
+    auth.id,
+    &filter,
+    &sort,
+  ) {
+    Ok(paginated) => HttpResponse::Ok().json(paginated),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
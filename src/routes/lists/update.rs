@@ -0,0 +1,47 @@
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use crate::validation::list_request::ListRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Rename the list
+///
+/// @param {String} list_id
+/// @param {String} name
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "5f0b7c1e-8d1a-4f4e-9d3b-2f7c9a6e4b10",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "name": "Work",
+///   "created_at": "2020-10-20T09:30:00Z",
+///   "updated_at": "2020-10-20T10:15:00Z"
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  data: Json<ListRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let list = match super::find_authorized(connection, &path.0, &auth, Access::Manage) {
+    Ok(list) => list,
+    Err(response) => return response,
+  };
+
+  match list.update_name(connection, &data.name) {
+    Ok(updated) => HttpResponse::Ok().json(updated),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
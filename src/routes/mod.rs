@@ -1,9 +1,23 @@
 pub mod auth;
+pub mod lists;
+pub mod tags;
 pub mod todos;
+pub mod trash;
 pub mod users;
 
+use crate::state::app::AppState;
 use actix_web::{HttpResponse, Responder};
 
 pub async fn sanity_check() -> impl Responder {
   HttpResponse::Ok().body("Hello world")
 }
+
+/// Requested page, pages are counted from the first one
+pub fn page(requested: Option<u32>) -> u32 {
+  requested.unwrap_or(1).max(1)
+}
+
+/// Requested number of items per page, capped by the configured maximum
+pub fn per_page(state: &AppState, requested: Option<u32>) -> u32 {
+  requested.unwrap_or(crate::DEFAULT_PER_PAGE).clamp(1, state.max_per_page())
+}
@@ -0,0 +1,31 @@
+use crate::models::tag::Tag;
+use crate::models::user::User;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Get all the tags of the user
+///
+/// Success code 200:
+/// ```
+/// [
+///   {
+///     "id": "9a3e5c2d-6b1f-4c8e-a7d4-3e2b1c0f9d8a",
+///     "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///     "name": "urgent",
+///     "created_at": "2020-10-20T09:30:00Z"
+///   }
+/// ]
+/// ```
+///
+/// Error: 400
+pub async fn handle(req: web::HttpRequest, state: web::Data<AppState>) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  match Tag::users(&state.get_connection(), &auth.id) {
+    Ok(tags) => HttpResponse::Ok().json(tags),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
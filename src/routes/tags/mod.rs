@@ -0,0 +1,26 @@
+pub mod index;
+pub mod store;
+
+use crate::models::tag::Tag;
+use crate::models::user::User;
+use actix_web::HttpResponse;
+
+/// Find the tag with given id and make sure that the authenticated user
+/// actually owns it. Error contains the response that should be returned.
+pub fn find_owned(
+  connection: &crate::diesel::PgConnection,
+  tag_id: &str,
+  auth: &User,
+) -> Result<Tag, HttpResponse> {
+  let tag = match Tag::show(connection, tag_id) {
+    Ok(tag) => tag,
+    Err(_) => return Err(HttpResponse::NotFound().finish()),
+  };
+
+  // Allow access only to tags that the user actually owns
+  if tag.user_id != auth.id {
+    return Err(HttpResponse::Forbidden().finish());
+  }
+
+  Ok(tag)
+}
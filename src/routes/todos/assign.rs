@@ -0,0 +1,69 @@
+use crate::models::user::User;
+use crate::services::authorization::{self, Access};
+use crate::state::app::AppState;
+use crate::validation::assign_todo_request::AssignTodoRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Assign the todo to the user, who then can view and complete it. Todos
+/// can be assigned only to the users that are able to see them, e.g.
+/// members of the list that the todo is on. Missing assignee leaves the
+/// todo unassigned.
+///
+/// @param {String} todo_id
+/// @param {String} [assignee_id]
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "content": "Do something",
+///   "checked": false,
+///   "created_at": "2020-10-20T09:30:00Z",
+///   "updated_at": "2020-10-20T10:15:00Z",
+///   "completed_at": null,
+///   "due_at": null,
+///   "priority": "normal",
+///   "position": 1.0,
+///   "list_id": null,
+///   "parent_id": null,
+///   "recurrence": null,
+///   "series_id": null,
+///   "deleted_at": null,
+///   "assignee_id": "7e6d5c4b-3a29-4180-9f8e-7d6c5b4a3928",
+///   "tags": []
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  data: Json<AssignTodoRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let todo = match super::find_authorized(connection, &path.0, &auth, Access::Edit) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  if let Some(assignee_id) = &data.assignee_id {
+    match authorization::can_access_todo(connection, &todo, assignee_id, Access::View) {
+      Ok(true) => {}
+      _ => return HttpResponse::BadRequest().finish(),
+    }
+  }
+
+  match todo.assign(connection, &auth.id, data.assignee_id.as_deref()) {
+    Ok(assigned) => super::todo_response(connection, assigned),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
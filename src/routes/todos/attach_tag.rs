@@ -0,0 +1,59 @@
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use crate::validation::attach_tag_request::AttachTagRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Attach the tag to the todo
+///
+/// @param {String} todo_id
+/// @param {String} tag_id
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "content": "Do something",
+///   ...
+///   "tags": [
+///     {
+///       "id": "9a3e5c2d-6b1f-4c8e-a7d4-3e2b1c0f9d8a",
+///       "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///       "name": "urgent",
+///       "created_at": "2020-10-20T09:30:00Z"
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  data: Json<AttachTagRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let todo = match super::find_authorized(connection, &path.0, &auth, Access::Edit) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  let tag = match crate::routes::tags::find_owned(connection, &data.tag_id, &auth) {
+    Ok(tag) => tag,
+    Err(response) => return response,
+  };
+
+  match tag.attach(connection, &todo.id) {
+    Ok(_) => super::todo_response(connection, todo),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -0,0 +1,43 @@
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Delete the attachment together with its content in the storage
+///
+/// @param {String} todo_id
+/// @param {String} attachment_id
+///
+/// Success code 204
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<(String, String)>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let (todo_id, attachment_id) = path.into_inner();
+  let connection = &state.get_connection();
+
+  let attachment =
+    match super::find_authorized(connection, &todo_id, &attachment_id, &auth, Access::Edit) {
+      Ok(attachment) => attachment,
+      Err(response) => return response,
+    };
+
+  if attachment.delete(connection).is_err() {
+    return HttpResponse::BadRequest().finish();
+  }
+
+  // The record is already gone, leftover file is only logged
+  if let Err(e) = state.storage().delete(&attachment.storage_key) {
+    println!("Attachments: Could not delete {}: {:?}", attachment.storage_key, e);
+  }
+
+  HttpResponse::NoContent().finish()
+}
@@ -0,0 +1,48 @@
+use crate::models::attachment::Attachment;
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Get all the files attached to the todo, the oldest come first
+///
+/// @param {String} todo_id
+///
+/// Success code 200:
+/// ```
+/// [
+///   {
+///     "id": "5b7d9f1a-3c5e-4a7b-9d1f-3a5c7e9b1d3f",
+///     "todo_id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///     "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///     "filename": "screenshot.png",
+///     "content_type": "image/png",
+///     "size": 48213,
+///     "created_at": "2020-10-20T09:30:00Z"
+///   }
+/// ]
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let todo = match super::super::find_authorized(connection, &path.0, &auth, Access::View) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  match Attachment::for_todo(connection, &todo.id) {
+    Ok(attachments) => HttpResponse::Ok().json(attachments),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
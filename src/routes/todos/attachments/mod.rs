@@ -0,0 +1,72 @@
+pub mod delete;
+pub mod index;
+pub mod show;
+pub mod store;
+
+use crate::models::attachment::Attachment;
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use actix_web::HttpResponse;
+
+/// Types that can be attached to the todos unless configured otherwise
+const DEFAULT_ALLOWED_TYPES: &str = "image/png,image/jpeg,image/gif,image/webp,application/pdf";
+
+/// Bytes expected at the offset in the file
+type Signature = &'static [(usize, &'static [u8])];
+
+/// Bytes the files of given type start with
+const SIGNATURES: &[(&str, Signature)] = &[
+  ("image/png", &[(0, b"\x89PNG\r\n\x1a\n")]),
+  ("image/jpeg", &[(0, b"\xFF\xD8\xFF")]),
+  ("image/gif", &[(0, b"GIF8")]),
+  ("image/webp", &[(0, b"RIFF"), (8, b"WEBP")]),
+  ("application/pdf", &[(0, b"%PDF-")]),
+];
+
+/// Find the attachment on the todo and make sure that the authenticated
+/// user is allowed the access on the todo. Error contains the response that
+/// should be returned.
+pub fn find_authorized(
+  connection: &crate::diesel::PgConnection,
+  todo_id: &str,
+  attachment_id: &str,
+  auth: &User,
+  access: Access,
+) -> Result<Attachment, HttpResponse> {
+  let todo = super::find_authorized(connection, todo_id, auth, access)?;
+
+  match Attachment::show(connection, attachment_id) {
+    Ok(attachment) if attachment.todo_id == todo.id => Ok(attachment),
+    _ => Err(HttpResponse::NotFound().finish()),
+  }
+}
+
+/// Maximum size of the single attachment in bytes, configured by the
+/// `ATTACHMENT_MAX_SIZE_IN_BYTES` variable
+pub fn max_size() -> usize {
+  dotenv::var("ATTACHMENT_MAX_SIZE_IN_BYTES")
+    .ok()
+    .and_then(|value| value.parse::<usize>().ok())
+    .unwrap_or(10 * 1024 * 1024)
+}
+
+/// Check if the type is in the comma separated `ATTACHMENT_ALLOWED_TYPES` variable
+pub fn is_allowed_type(content_type: &str) -> bool {
+  let allowed =
+    dotenv::var("ATTACHMENT_ALLOWED_TYPES").unwrap_or_else(|_| DEFAULT_ALLOWED_TYPES.into());
+
+  allowed
+    .split(',')
+    .any(|allowed| allowed.trim().eq_ignore_ascii_case(content_type))
+}
+
+/// Check that the content of the file is of the declared type, types that
+/// can not be recognized by their content are trusted
+pub fn matches_content(content_type: &str, bytes: &[u8]) -> bool {
+  match SIGNATURES.iter().find(|(signed, _)| *signed == content_type) {
+    Some((_, parts)) => parts
+      .iter()
+      .all(|(offset, expected)| bytes.get(*offset..offset + expected.len()) == Some(*expected)),
+    None => true,
+  }
+}
@@ -0,0 +1,45 @@
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::http::header::{self, ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{web, HttpResponse, Responder};
+
+/// Download the content of the attachment
+///
+/// @param {String} todo_id
+/// @param {String} attachment_id
+///
+/// Success code 200 with the file in the body
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<(String, String)>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let (todo_id, attachment_id) = path.into_inner();
+  let connection = &state.get_connection();
+
+  let attachment =
+    match super::find_authorized(connection, &todo_id, &attachment_id, &auth, Access::View) {
+      Ok(attachment) => attachment,
+      Err(response) => return response,
+    };
+
+  match state.storage().get(&attachment.storage_key) {
+    Ok(bytes) => HttpResponse::Ok()
+      .content_type(attachment.content_type)
+      .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+      .set(ContentDisposition {
+        disposition: DispositionType::Attachment,
+        parameters: vec![DispositionParam::Filename(attachment.filename)],
+      })
+      .body(bytes),
+    Err(_) => HttpResponse::NotFound().finish(),
+  }
+}
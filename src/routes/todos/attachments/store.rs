@@ -0,0 +1,122 @@
+use crate::models::attachment::NewAttachment;
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_multipart::Multipart;
+use actix_web::{web, HttpResponse, Responder};
+use futures::{StreamExt, TryStreamExt};
+use uuid::Uuid;
+
+/// Upload the file and attach it to the todo. The file is sent as the `file`
+/// field of the multipart form, other fields are ignored. Content of the file
+/// has to match its declared type.
+///
+/// @param {String} todo_id
+/// @param {File} file
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "5b7d9f1a-3c5e-4a7b-9d1f-3a5c7e9b1d3f",
+///   "todo_id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "filename": "screenshot.png",
+///   "content_type": "image/png",
+///   "size": 48213,
+///   "created_at": "2020-10-20T09:30:00Z"
+/// }
+/// ```
+///
+/// Error: 400, 403, 404, 413 or 415
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  mut payload: Multipart,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let todo = match super::super::find_authorized(connection, &path.0, &auth, Access::Edit) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  let max_size = super::max_size();
+
+  while let Ok(Some(mut field)) = payload.try_next().await {
+    let disposition = match field.content_disposition() {
+      Some(disposition) if disposition.get_name() == Some("file") => disposition,
+      _ => continue,
+    };
+
+    let content_type = field.content_type().essence_str().to_lowercase();
+    if !super::is_allowed_type(&content_type) {
+      return HttpResponse::UnsupportedMediaType().finish();
+    }
+
+    let mut bytes: Vec<u8> = vec![];
+    while let Some(chunk) = field.next().await {
+      let chunk = match chunk {
+        Ok(chunk) => chunk,
+        Err(_) => return HttpResponse::BadRequest().finish(),
+      };
+
+      if bytes.len() + chunk.len() > max_size {
+        return HttpResponse::PayloadTooLarge().finish();
+      }
+
+      bytes.extend_from_slice(&chunk);
+    }
+
+    if !super::matches_content(&content_type, &bytes) {
+      return HttpResponse::UnsupportedMediaType().finish();
+    }
+
+    let attachment = NewAttachment {
+      todo_id: todo.id.clone(),
+      user_id: auth.id.clone(),
+      filename: filename(disposition.get_filename()),
+      content_type,
+      size: bytes.len() as i64,
+      storage_key: format!("{}/{}", todo.id, Uuid::new_v4()),
+    };
+
+    if state.storage().put(&attachment.storage_key, &bytes).is_err() {
+      return HttpResponse::InternalServerError().finish();
+    }
+
+    return match attachment.insert(connection) {
+      Ok(attachment) => HttpResponse::Ok().json(attachment),
+      Err(_) => {
+        // Do not leave the file behind when there is no record of it
+        let _ = state.storage().delete(&attachment.storage_key);
+        HttpResponse::BadRequest().finish()
+      }
+    };
+  }
+
+  HttpResponse::BadRequest().finish()
+}
+
+/// Keep only the name of the uploaded file without any directories,
+/// browsers on windows might send the whole path
+fn filename(uploaded: Option<&str>) -> String {
+  let name: String = uploaded
+    .and_then(|name| name.rsplit(['/', '\\']).next())
+    .unwrap_or("")
+    .chars()
+    .filter(|c| !c.is_control())
+    .take(255)
+    .collect();
+
+  if name.is_empty() {
+    String::from("attachment")
+  } else {
+    name
+  }
+}
@@ -0,0 +1,65 @@
+use crate::models::todo::{BulkAction, Todo};
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use crate::validation::bulk_todo_request::BulkTodoRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Apply single action to many todos at once
+///
+/// @param {Vec<String>} ids
+/// @param {String} action one of check, uncheck, delete, move or tag
+/// @param {String} [list_id] list to move the todos to, none takes them off the list
+/// @param {String} [tag_id] tag to attach to the todos
+///
+/// Success code 200:
+/// ```
+/// {
+///   "succeeded": ["06b8ff8c-3e34-4226-b917-cb07bd98785e"],
+///   "failed": [
+///     {
+///       "id": "7c1e4f2a-8d3b-4a6e-9f5c-2b1a0d9e8c7f",
+///       "error": "forbidden"
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  data: Json<BulkTodoRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  // Lists have to be editable and tags owned by the user before touching any todo
+  match &data.action {
+    BulkAction::Move {
+      list_id: Some(list_id),
+    } => {
+      if let Err(response) =
+        crate::routes::lists::find_authorized(connection, list_id, &auth, Access::Edit)
+      {
+        return response;
+      }
+    }
+    BulkAction::Tag { tag_id } => {
+      if let Err(response) = crate::routes::tags::find_owned(connection, tag_id, &auth) {
+        return response;
+      }
+    }
+    _ => {}
+  }
+
+  match Todo::bulk(connection, &auth.id, &data.ids, &data.action) {
+    Ok(outcome) => HttpResponse::Ok().json(outcome),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
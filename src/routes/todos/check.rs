@@ -1,11 +1,17 @@
-use crate::models::todo::Todo;
 use crate::models::user::User;
+use crate::services::authorization::Access;
 use crate::state::app::AppState;
 use actix_web::{web, HttpResponse, Responder};
 
+#[derive(serde::Deserialize)]
+pub struct CheckTodoRequest {
+  cascade: Option<bool>,
+}
+
 /// Check the todo
 ///
 /// @param {String} todo_id
+/// @param {bool} [cascade] check all the subtasks of the todo as well
 ///
 /// Success code 200:
 /// ```
@@ -13,7 +19,20 @@ use actix_web::{web, HttpResponse, Responder};
 ///   "id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
 ///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
 ///   "content": "Do something",
-///   "checked": true
+///   "checked": true,
+///   "created_at": "2020-10-20T09:30:00Z",
+///   "updated_at": "2020-10-20T10:15:00Z",
+///   "completed_at": "2020-10-20T10:15:00Z",
+///   "due_at": null,
+///   "priority": "normal",
+///   "position": 1.0,
+///   "list_id": null,
+///   "parent_id": null,
+///   "recurrence": null,
+///   "series_id": null,
+///   "deleted_at": null,
+///   "assignee_id": null,
+///   "tags": []
 /// }
 /// ```
 ///
@@ -21,6 +40,7 @@ use actix_web::{web, HttpResponse, Responder};
 pub async fn handle(
   req: web::HttpRequest,
   path: web::Path<String>,
+  query: web::Query<CheckTodoRequest>,
   state: web::Data<AppState>,
 ) -> impl Responder {
   let connection = state.get_connection();
@@ -29,21 +49,13 @@ pub async fn handle(
     None => return HttpResponse::BadRequest().finish(),
   };
 
-  let mut todo = match Todo::show(&connection, &path.0) {
+  let todo = match super::find_authorized(&connection, &path.0, &auth, Access::Complete) {
     Ok(todo) => todo,
-    Err(_) => return HttpResponse::NotFound().finish(),
+    Err(response) => return response,
   };
 
-  // Allow change only to todos that the user actually owns
-  if &todo.user_id != &auth.id {
-    return HttpResponse::Forbidden().finish();
-  }
-
-  match todo.check(&connection) {
-    Ok(_) => {
-      todo.checked = true;
-      HttpResponse::Ok().json(todo)
-    }
+  match todo.check(&connection, &auth.id, query.cascade.unwrap_or(false)) {
+    Ok(updated) => super::todo_response(&connection, updated),
     Err(_) => HttpResponse::BadRequest().finish(),
   }
 }
@@ -0,0 +1,35 @@
+use crate::models::user::User;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Delete own comment
+///
+/// @param {String} todo_id
+/// @param {String} comment_id
+///
+/// Success code 204
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<(String, String)>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let (todo_id, comment_id) = path.into_inner();
+  let connection = &state.get_connection();
+
+  let comment = match super::find_own(connection, &todo_id, &comment_id, &auth) {
+    Ok(comment) => comment,
+    Err(response) => return response,
+  };
+
+  match comment.delete(connection) {
+    Ok(_) => HttpResponse::NoContent().finish(),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -0,0 +1,65 @@
+use crate::models::comment::Comment;
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+#[derive(serde::Deserialize)]
+pub struct PaginatedCommentRequest {
+  page: Option<u32>,
+  per_page: Option<u32>,
+}
+
+/// Get paginated comments on the todo, the oldest come first
+///
+/// @param {String} todo_id
+/// @param {u32} [page]
+/// @param {u32} [per_page]
+///
+/// Success code 200:
+/// ```
+/// {
+///   "page": 1,
+///   "per_page": 10,
+///   "total": 1,
+///   "last_page": 1,
+///   "data": [
+///     {
+///       "id": "2d4f6a8c-1e3b-4d5f-8a7c-9e1b3d5f7a9c",
+///       "todo_id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///       "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///       "content": "Almost there",
+///       "created_at": "2020-10-20T09:30:00Z",
+///       "updated_at": "2020-10-20T09:30:00Z"
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  query: web::Query<PaginatedCommentRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let todo = match super::super::find_authorized(connection, &path.0, &auth, Access::View) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  let page = crate::routes::page(query.page);
+  let per_page = crate::routes::per_page(&state, query.per_page);
+
+  match Comment::paginated(connection, page, per_page, &todo.id) {
+    Ok(paginated) => HttpResponse::Ok().json(paginated),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -0,0 +1,33 @@
+pub mod delete;
+pub mod index;
+pub mod store;
+pub mod update;
+
+use crate::models::comment::Comment;
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use actix_web::HttpResponse;
+
+/// Find the comment on the todo and make sure that the authenticated user
+/// wrote it and can still see the todo. Error contains the response that
+/// should be returned.
+pub fn find_own(
+  connection: &crate::diesel::PgConnection,
+  todo_id: &str,
+  comment_id: &str,
+  auth: &User,
+) -> Result<Comment, HttpResponse> {
+  let todo = super::find_authorized(connection, todo_id, auth, Access::View)?;
+
+  let comment = match Comment::show(connection, comment_id) {
+    Ok(comment) if comment.todo_id == todo.id => comment,
+    _ => return Err(HttpResponse::NotFound().finish()),
+  };
+
+  // Allow changes only on comments that the user actually wrote
+  if comment.user_id != auth.id {
+    return Err(HttpResponse::Forbidden().finish());
+  }
+
+  Ok(comment)
+}
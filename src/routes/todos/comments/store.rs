@@ -0,0 +1,49 @@
+use crate::models::comment::NewComment;
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use crate::validation::comment_request::CommentRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Comment on the todo, anyone who can see the todo can comment on it
+///
+/// @param {String} todo_id
+/// @param {String} content
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "2d4f6a8c-1e3b-4d5f-8a7c-9e1b3d5f7a9c",
+///   "todo_id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "content": "Almost there",
+///   "created_at": "2020-10-20T09:30:00Z",
+///   "updated_at": "2020-10-20T09:30:00Z"
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  data: Json<CommentRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let todo = match super::super::find_authorized(connection, &path.0, &auth, Access::View) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  match NewComment::create(connection, &todo.id, &auth.id, &data.content) {
+    Ok(comment) => HttpResponse::Ok().json(comment),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -0,0 +1,49 @@
+use crate::models::user::User;
+use crate::state::app::AppState;
+use crate::validation::comment_request::CommentRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Edit the content of own comment
+///
+/// @param {String} todo_id
+/// @param {String} comment_id
+/// @param {String} content
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "2d4f6a8c-1e3b-4d5f-8a7c-9e1b3d5f7a9c",
+///   "todo_id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "content": "Done, finally",
+///   "created_at": "2020-10-20T09:30:00Z",
+///   "updated_at": "2020-10-20T10:15:00Z"
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<(String, String)>,
+  data: Json<CommentRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let (todo_id, comment_id) = path.into_inner();
+  let connection = &state.get_connection();
+
+  let comment = match super::find_own(connection, &todo_id, &comment_id, &auth) {
+    Ok(comment) => comment,
+    Err(response) => return response,
+  };
+
+  match comment.update_content(connection, &data.content) {
+    Ok(updated) => HttpResponse::Ok().json(updated),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
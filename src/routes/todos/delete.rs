@@ -0,0 +1,34 @@
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Move the todo together with its subtasks to the trash
+///
+/// @param {String} todo_id
+///
+/// Success code 204
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let todo = match super::find_authorized(connection, &path.0, &auth, Access::Edit) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  match todo.delete(connection, &auth.id) {
+    Ok(_) => HttpResponse::NoContent().finish(),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
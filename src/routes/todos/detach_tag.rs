@@ -0,0 +1,50 @@
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Detach the tag from the todo
+///
+/// @param {String} todo_id
+/// @param {String} tag_id
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "content": "Do something",
+///   ...
+///   "tags": []
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<(String, String)>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+  let (todo_id, tag_id) = path.into_inner();
+
+  let todo = match super::find_authorized(connection, &todo_id, &auth, Access::Edit) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  let tag = match crate::routes::tags::find_owned(connection, &tag_id, &auth) {
+    Ok(tag) => tag,
+    Err(response) => return response,
+  };
+
+  match tag.detach(connection, &todo.id) {
+    Ok(_) => super::todo_response(connection, todo),
+    Err(_) => HttpResponse::NotFound().finish(),
+  }
+}
@@ -0,0 +1,83 @@
+use crate::models::todo::Todo;
+use crate::models::todo_event::TodoEvent;
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+#[derive(serde::Deserialize)]
+pub struct PaginatedHistoryRequest {
+  page: Option<u32>,
+  per_page: Option<u32>,
+}
+
+/// Get paginated history of the todo, the oldest changes come first. History
+/// of the todos in the trash can be read as well.
+///
+/// @param {String} todo_id
+/// @param {u32} [page]
+/// @param {u32} [per_page]
+///
+/// Success code 200:
+/// ```
+/// {
+///   "page": 1,
+///   "per_page": 10,
+///   "total": 2,
+///   "last_page": 1,
+///   "data": [
+///     {
+///       "id": "3f2b1c0d-9e8a-4b7c-a6d5-e4f3a2b1c0d9",
+///       "todo_id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///       "actor_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///       "kind": "create",
+///       "old_value": null,
+///       "new_value": { "content": "Do something" },
+///       "created_at": "2020-10-20T09:30:00Z"
+///     },
+///     {
+///       "id": "8a7b6c5d-4e3f-4a2b-9c1d-0e9f8a7b6c5d",
+///       "todo_id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///       "actor_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///       "kind": "check",
+///       "old_value": { "checked": false },
+///       "new_value": { "checked": true },
+///       "created_at": "2020-10-20T10:15:00Z"
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  query: web::Query<PaginatedHistoryRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  // History stays readable while the todo is in the trash
+  let todo = match Todo::show_with_trashed(connection, &path.0) {
+    Ok(todo) => todo,
+    Err(_) => return HttpResponse::NotFound().finish(),
+  };
+
+  let todo = match super::authorize(connection, todo, &auth, Access::View) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  let page = crate::routes::page(query.page);
+  let per_page = crate::routes::per_page(&state, query.per_page);
+
+  match TodoEvent::history(connection, page, per_page, &todo.id) {
+    Ok(paginated) => HttpResponse::Ok().json(paginated),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
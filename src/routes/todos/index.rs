@@ -1,20 +1,122 @@
-use crate::models::todo::Todo;
+use crate::models::priority::Priority;
+use crate::models::todo::{
+  TagMatch, Todo, TodoCursor, TodoFilter, TodoPage, TodoSort, TodoStatus,
+};
 use crate::models::user::User;
 use crate::state::app::AppState;
 use actix_web::{web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
 
 #[derive(serde::Deserialize)]
 pub struct PaginatedTodoRequest {
   page: Option<u32>,
   per_page: Option<u32>,
+  cursor: Option<String>,
+  status: Option<TodoStatus>,
   checked: Option<bool>,
+  overdue: Option<bool>,
+  created_before: Option<DateTime<Utc>>,
+  created_after: Option<DateTime<Utc>>,
+  due_before: Option<DateTime<Utc>>,
+  due_after: Option<DateTime<Utc>>,
+  list_id: Option<String>,
+  priority: Option<Priority>,
+  assigned_to: Option<String>,
+  tags: Option<String>,
+  tag_match: Option<TagMatch>,
+  include_subtasks: Option<bool>,
+  sort: Option<String>,
 }
 
-/// Authenticate the user with email and password
+impl PaginatedTodoRequest {
+  /// Requested page, the cursor takes precedence over the page number and
+  /// has to be issued for the same sorting
+  pub fn page(&self, sort: &TodoSort) -> Result<TodoPage, String> {
+    match &self.cursor {
+      Some(value) => {
+        let cursor = TodoCursor::decode(value)?;
+        if cursor.sort() != *sort {
+          return Err("Cursor was issued for different sorting".into());
+        }
+        Ok(TodoPage::After(cursor))
+      }
+      None => Ok(TodoPage::Number(crate::routes::page(self.page))),
+    }
+  }
+
+  /// Requested number of todos per page
+  pub fn per_page(&self, state: &AppState) -> u32 {
+    crate::routes::per_page(state, self.per_page)
+  }
+
+  /// Requested status, the older `checked` flag is still understood when
+  /// the status is not given
+  pub fn status(&self) -> TodoStatus {
+    match (self.status, self.checked) {
+      (Some(status), _) => status,
+      (None, Some(true)) => TodoStatus::Done,
+      (None, _) => TodoStatus::Open,
+    }
+  }
+
+  /// Filters requested through the query, `me` in place of the assignee
+  /// stands for the authenticated user
+  pub fn filter(&self, user_id: &str) -> TodoFilter {
+    TodoFilter {
+      status: self.status(),
+      overdue: self.overdue.unwrap_or(false),
+      created_before: self.created_before,
+      created_after: self.created_after,
+      due_before: self.due_before,
+      due_after: self.due_after,
+      list_id: self.list_id.clone(),
+      priority: self.priority,
+      assignee_id: match self.assigned_to.as_deref() {
+        Some("me") => Some(String::from(user_id)),
+        assignee => assignee.map(String::from),
+      },
+      tags: match &self.tags {
+        Some(tags) => tags
+          .split(',')
+          .filter(|tag| !tag.is_empty())
+          .map(String::from)
+          .collect(),
+        None => vec![],
+      },
+      tag_match: self.tag_match.unwrap_or_default(),
+      include_subtasks: self.include_subtasks.unwrap_or(false),
+    }
+  }
+
+  /// Requested sorting, error is returned for unknown sort fields
+  pub fn sort(&self) -> Result<TodoSort, String> {
+    match &self.sort {
+      Some(value) => value.parse::<TodoSort>(),
+      None => Ok(TodoSort::default()),
+    }
+  }
+}
+
+/// Get paginated todos of the user
 ///
 /// @param {u32} [page]
 /// @param {u32} [per_page]
-/// @param {bool} [checked]
+/// @param {String} [cursor] `next_cursor` of the previous page, replaces the page number
+/// @param {String} [status] one of all, open or done, open by default
+/// @param {bool} [checked] deprecated, same as done or open status
+/// @param {bool} [overdue]
+/// @param {DateTime} [created_before]
+/// @param {DateTime} [created_after]
+/// @param {DateTime} [due_before]
+/// @param {DateTime} [due_after]
+/// @param {String} [list_id]
+/// @param {String} [priority] one of low, normal, high or urgent
+/// @param {String} [assigned_to] id of the assignee or `me`
+/// @param {String} [tags] comma separated tag ids
+/// @param {String} [tag_match] either any or all of the tags, any by default
+/// @param {bool} [include_subtasks] list the subtasks next to the todos, false by default
+/// @param {String} [sort] one of priority, created_at, due_at, content or position,
+///   prefixed with `-` for descending order
 ///
 /// Success code 200:
 /// ```
@@ -23,7 +125,8 @@ pub struct PaginatedTodoRequest {
 ///   "per_page": 10,
 ///   "total": 120,
 ///   "last_page": 12,
-///   "data": [ ... ]
+///   "data": [ ... ],
+///   "next_cursor": "eyJwYWdlIjoyLCJrZXkiOnsi..."
 /// }
 /// ```
 ///
@@ -38,22 +141,26 @@ pub async fn handle(
     None => return HttpResponse::BadRequest().finish(),
   };
 
-  let page = match query.page {
-    Some(n) => n,
-    None => 1,
+  let sort = match query.sort() {
+    Ok(sort) => sort,
+    Err(_) => return HttpResponse::BadRequest().finish(),
   };
 
-  let per_page = match query.per_page {
-    Some(n) => n,
-    None => crate::DEFAULT_PER_PAGE,
+  let page = match query.page(&sort) {
+    Ok(page) => page,
+    Err(_) => return HttpResponse::BadRequest().finish(),
   };
 
-  let checked = match query.checked {
-    Some(c) => c,
-    None => false,
-  };
+  let filter = query.filter(&auth.id);
 
-  match Todo::paginated(&state.get_connection(), page, per_page, auth.id, checked) {
+  match Todo::paginated(
+    &state.get_connection(),
+    &page,
+    query.per_page(&state),
+    auth.id,
+    &filter,
+    &sort,
+  ) {
     Ok(paginated) => HttpResponse::Ok().json(paginated),
     Err(_) => HttpResponse::BadRequest().finish(),
   }
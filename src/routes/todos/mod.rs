@@ -1,4 +1,60 @@
+pub mod assign;
+pub mod attach_tag;
+pub mod attachments;
+pub mod bulk;
 pub mod check;
+pub mod comments;
+pub mod delete;
+pub mod detach_tag;
+pub mod history;
 pub mod index;
+pub mod reorder;
+pub mod restore;
+pub mod search;
+pub mod show;
+pub mod stop_recurrence;
 pub mod store;
+pub mod subtasks;
 pub mod uncheck;
+pub mod update;
+
+use crate::models::todo::Todo;
+use crate::models::user::User;
+use crate::services::authorization::{self, Access};
+use actix_web::HttpResponse;
+
+/// Find the todo with given id and make sure that the authenticated user
+/// is allowed the access on it. Error contains the response that should be returned.
+pub fn find_authorized(
+  connection: &crate::diesel::PgConnection,
+  todo_id: &str,
+  auth: &User,
+  access: Access,
+) -> Result<Todo, HttpResponse> {
+  match Todo::show(connection, todo_id) {
+    Ok(todo) => authorize(connection, todo, auth, access),
+    Err(_) => Err(HttpResponse::NotFound().finish()),
+  }
+}
+
+/// Make sure that the authenticated user is allowed the access on the todo
+pub fn authorize(
+  connection: &crate::diesel::PgConnection,
+  todo: Todo,
+  auth: &User,
+  access: Access,
+) -> Result<Todo, HttpResponse> {
+  match authorization::can_access_todo(connection, &todo, &auth.id, access) {
+    Ok(true) => Ok(todo),
+    Ok(false) => Err(HttpResponse::Forbidden().finish()),
+    Err(_) => Err(HttpResponse::BadRequest().finish()),
+  }
+}
+
+/// Respond with the todo together with its tags
+pub fn todo_response(connection: &crate::diesel::PgConnection, todo: Todo) -> HttpResponse {
+  match todo.with_tags(connection) {
+    Ok(todo) => HttpResponse::Ok().json(todo),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
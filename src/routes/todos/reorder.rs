@@ -0,0 +1,69 @@
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use crate::validation::move_todo_request::MoveTodoRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Move the todo in the manually ordered list. When both neighbours are given
+/// they have to be next to each other, otherwise the list changed meanwhile.
+///
+/// @param {String} todo_id
+/// @param {String} [after] id of the todo that will come right before the moved one
+/// @param {String} [before] id of the todo that will come right after the moved one
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "content": "Do something",
+///   "checked": false,
+///   "created_at": "2020-10-20T09:30:00Z",
+///   "updated_at": "2020-10-20T10:15:00Z",
+///   "completed_at": null,
+///   "due_at": null,
+///   "priority": "normal",
+///   "position": 2.5,
+///   "list_id": null,
+///   "parent_id": null,
+///   "recurrence": null,
+///   "series_id": null,
+///   "deleted_at": null,
+///   "assignee_id": null,
+///   "tags": []
+/// }
+/// ```
+///
+/// Error: 400, 403, 404 or 409
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  data: Json<MoveTodoRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let todo = match super::find_authorized(connection, &path.0, &auth, Access::Edit) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  // Neighbours have to be user todos as well
+  for neighbour in data.after.iter().chain(data.before.iter()) {
+    if let Err(response) = super::find_authorized(connection, neighbour, &auth, Access::View) {
+      return response;
+    }
+  }
+
+  match todo.move_between(connection, &auth.id, data.after.as_deref(), data.before.as_deref()) {
+    Ok(Some(moved)) => super::todo_response(connection, moved),
+    Ok(None) => HttpResponse::Conflict().finish(),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
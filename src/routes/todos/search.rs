@@ -0,0 +1,60 @@
+use crate::models::todo::Todo;
+use crate::models::user::User;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+#[derive(serde::Deserialize)]
+pub struct SearchTodoRequest {
+  q: String,
+  page: Option<u32>,
+  per_page: Option<u32>,
+}
+
+/// Full text search over the content of the user todos
+///
+/// @param {String} q words to search for, supports "quoted phrases" and -exclusions
+/// @param {u32} [page]
+/// @param {u32} [per_page]
+///
+/// Success code 200:
+/// ```
+/// {
+///   "page": 1,
+///   "per_page": 10,
+///   "total": 2,
+///   "last_page": 1,
+///   "data": [
+///     {
+///       "id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///       "content": "Buy milk and bread",
+///       ...
+///       "rank": 0.0607927,
+///       "snippet": "Buy <mark>milk</mark> and bread"
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Error: 400
+pub async fn handle(
+  req: web::HttpRequest,
+  query: web::Query<SearchTodoRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  if query.q.trim().is_empty() {
+    return HttpResponse::BadRequest().finish();
+  }
+
+  let page = crate::routes::page(query.page);
+  let per_page = crate::routes::per_page(&state, query.per_page);
+
+  match Todo::search(&state.get_connection(), page, per_page, &auth.id, &query.q) {
+    Ok(paginated) => HttpResponse::Ok().json(paginated),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -0,0 +1,55 @@
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+/// Stop the recurring todo, no more occurrences of its series are created
+///
+/// @param {String} todo_id
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "content": "Water the plants",
+///   "checked": false,
+///   "created_at": "2020-10-20T09:30:00Z",
+///   "updated_at": "2020-10-20T10:15:00Z",
+///   "completed_at": null,
+///   "due_at": "2020-10-27T09:00:00Z",
+///   "priority": "normal",
+///   "position": 1.0,
+///   "list_id": null,
+///   "parent_id": null,
+///   "recurrence": null,
+///   "series_id": "b2c4e6a8-0f1d-4b3c-9e5a-7d9f1b3c5e7a",
+///   "deleted_at": null,
+///   "assignee_id": null,
+///   "tags": []
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let todo = match super::find_authorized(connection, &path.0, &auth, Access::Edit) {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  match todo.stop_recurrence(connection, &auth.id) {
+    Ok(updated) => super::todo_response(connection, updated),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
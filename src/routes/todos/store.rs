@@ -1,5 +1,6 @@
 use crate::models::todo::NewTodo;
 use crate::models::user::User;
+use crate::services::authorization::Access;
 use crate::state::app::AppState;
 use crate::validation::new_todo_request::NewTodoRequest;
 use actix_web::{web, HttpResponse, Responder};
@@ -8,6 +9,10 @@ use actix_web_validator::Json;
 /// Create new todo
 ///
 /// @param {String} content
+/// @param {DateTime} [due_at]
+/// @param {String} [priority] one of low, normal, high or urgent
+/// @param {String} [recurrence] rule such as `FREQ=WEEKLY;BYDAY=MO,WE`, requires due_at
+/// @param {String} [list_id]
 ///
 /// Success code 200:
 /// ```
@@ -15,11 +20,24 @@ use actix_web_validator::Json;
 ///   "id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
 ///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
 ///   "content": "Do something",
-///   "checked": false
+///   "checked": false,
+///   "created_at": "2020-10-20T09:30:00Z",
+///   "updated_at": "2020-10-20T10:15:00Z",
+///   "completed_at": null,
+///   "due_at": null,
+///   "priority": "normal",
+///   "position": 1.0,
+///   "list_id": null,
+///   "parent_id": null,
+///   "recurrence": null,
+///   "series_id": null,
+///   "deleted_at": null,
+///   "assignee_id": null,
+///   "tags": []
 /// }
 /// ```
 ///
-/// Error: 400
+/// Error: 400, 403 or 404
 pub async fn handle(
   req: web::HttpRequest,
   data: Json<NewTodoRequest>,
@@ -35,8 +53,25 @@ pub async fn handle(
     None => "".into(),
   };
 
-  match NewTodo::create(&state.get_connection(), &auth.id, &content) {
-    Ok(todo) => HttpResponse::Ok().json(todo),
+  let connection = &state.get_connection();
+
+  // Todos can be put only on the lists that the user is allowed to edit
+  if let Some(list_id) = &data.list_id {
+    if let Err(response) =
+      crate::routes::lists::find_authorized(connection, list_id, &auth, Access::Edit)
+    {
+      return response;
+    }
+  }
+
+  let mut todo = NewTodo::new(&auth.id, &content);
+  todo.due_at = data.due_at;
+  todo.priority = data.priority.unwrap_or_default();
+  todo.recurrence = data.recurrence.clone();
+  todo.list_id = data.list_id.clone();
+
+  match todo.insert(connection) {
+    Ok(todo) => super::todo_response(connection, todo),
     Err(_) => HttpResponse::BadRequest().finish(),
   }
 }
@@ -0,0 +1,69 @@
+use crate::models::todo::{Progress, TodoWithTags};
+use crate::models::user::User;
+use crate::services::authorization::{self, Access};
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+#[derive(serde::Serialize)]
+struct SubtasksResponse {
+  progress: Progress,
+  data: Vec<TodoWithTags>,
+}
+
+/// Get subtasks of the todo together with the progress on them, only the
+/// subtasks the user is allowed to view are listed and counted
+///
+/// @param {String} todo_id
+///
+/// Success code 200:
+/// ```
+/// {
+///   "progress": {
+///     "done": 3,
+///     "total": 5
+///   },
+///   "data": [ ... ]
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let todo = match crate::routes::todos::find_authorized(connection, &path.0, &auth, Access::View)
+  {
+    Ok(todo) => todo,
+    Err(response) => return response,
+  };
+
+  let subtasks = match todo.subtasks(connection) {
+    Ok(subtasks) => subtasks,
+    Err(_) => return HttpResponse::BadRequest().finish(),
+  };
+
+  // Subtasks might be on a list that is not shared with the user
+  let mut visible = vec![];
+  for subtask in subtasks {
+    match authorization::can_access_todo(connection, &subtask, &auth.id, Access::View) {
+      Ok(true) => visible.push(subtask),
+      Ok(false) => {}
+      Err(_) => return HttpResponse::BadRequest().finish(),
+    }
+  }
+
+  let progress = Progress::of(&visible);
+
+  match TodoWithTags::load(connection, visible) {
+    Ok(data) => HttpResponse::Ok().json(SubtasksResponse { progress, data }),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -0,0 +1,69 @@
+use crate::models::todo::NewTodo;
+use crate::models::user::User;
+use crate::services::authorization::Access;
+use crate::state::app::AppState;
+use crate::validation::new_todo_request::NewTodoRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Create new subtask of the todo, it is put on the same list as its parent
+///
+/// @param {String} todo_id
+/// @param {String} content
+/// @param {DateTime} [due_at]
+/// @param {String} [priority] one of low, normal, high or urgent
+/// @param {String} [recurrence] rule such as `FREQ=WEEKLY;BYDAY=MO,WE`, requires due_at
+///
+/// Success code 200:
+/// ```
+/// {
+///   "id": "7c1d2e3f-4a5b-4c6d-8e9f-0a1b2c3d4e5f",
+///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
+///   "content": "Do a part of something",
+///   ...
+///   "parent_id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
+///   "recurrence": null,
+///   "series_id": null,
+///   "deleted_at": null,
+///   "assignee_id": null,
+///   "tags": []
+/// }
+/// ```
+///
+/// Error: 400, 403 or 404
+pub async fn handle(
+  req: web::HttpRequest,
+  path: web::Path<String>,
+  data: Json<NewTodoRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let connection = &state.get_connection();
+
+  let parent =
+    match crate::routes::todos::find_authorized(connection, &path.0, &auth, Access::Edit) {
+      Ok(todo) => todo,
+      Err(response) => return response,
+    };
+
+  let content: String = match &data.content {
+    Some(c) => c.into(),
+    None => "".into(),
+  };
+
+  let mut todo = NewTodo::new(&auth.id, &content);
+  todo.due_at = data.due_at;
+  todo.priority = data.priority.unwrap_or_default();
+  todo.recurrence = data.recurrence.clone();
+  todo.list_id = parent.list_id.clone();
+  todo.parent_id = Some(parent.id);
+
+  match todo.insert(connection) {
+    Ok(todo) => crate::routes::todos::todo_response(connection, todo),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -1,5 +1,5 @@
-use crate::models::todo::Todo;
 use crate::models::user::User;
+use crate::services::authorization::Access;
 use crate::state::app::AppState;
 use actix_web::{web, HttpResponse, Responder};
 
@@ -13,7 +13,20 @@ use actix_web::{web, HttpResponse, Responder};
 ///   "id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
 ///   "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
 ///   "content": "Do something",
-///   "checked": false
+///   "checked": false,
+///   "created_at": "2020-10-20T09:30:00Z",
+///   "updated_at": "2020-10-20T10:15:00Z",
+///   "completed_at": null,
+///   "due_at": null,
+///   "priority": "normal",
+///   "position": 1.0,
+///   "list_id": null,
+///   "parent_id": null,
+///   "recurrence": null,
+///   "series_id": null,
+///   "deleted_at": null,
+///   "assignee_id": null,
+///   "tags": []
 /// }
 /// ```
 ///
@@ -30,21 +43,13 @@ pub async fn handle(
 
   let connection = &state.get_connection();
 
-  let mut todo = match Todo::show(connection, &path.0) {
+  let todo = match super::find_authorized(connection, &path.0, &auth, Access::Complete) {
     Ok(todo) => todo,
-    Err(_) => return HttpResponse::NotFound().finish(),
+    Err(response) => return response,
   };
 
-  // Allow change only to todos that the user actually owns
-  if &todo.user_id != &auth.id {
-    return HttpResponse::Forbidden().finish();
-  }
-
-  match todo.uncheck(connection) {
-    Ok(_) => {
-      todo.checked = false;
-      HttpResponse::Ok().json(todo)
-    }
+  match todo.uncheck(connection, &auth.id) {
+    Ok(updated) => super::todo_response(connection, updated),
     Err(_) => HttpResponse::BadRequest().finish(),
   }
 }
@@ -0,0 +1,46 @@
+use crate::models::todo::Todo;
+use crate::models::user::User;
+use crate::state::app::AppState;
+use actix_web::{web, HttpResponse, Responder};
+
+#[derive(serde::Deserialize)]
+pub struct PaginatedTrashRequest {
+  page: Option<u32>,
+  per_page: Option<u32>,
+}
+
+/// Get paginated trashed todos of the user, the latest trashed come first
+///
+/// @param {u32} [page]
+/// @param {u32} [per_page]
+///
+/// Success code 200:
+/// ```
+/// {
+///   "page": 1,
+///   "per_page": 10,
+///   "total": 2,
+///   "last_page": 1,
+///   "data": [ ... ]
+/// }
+/// ```
+///
+/// Error: 400
+pub async fn handle(
+  req: web::HttpRequest,
+  query: web::Query<PaginatedTrashRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let page = crate::routes::page(query.page);
+  let per_page = crate::routes::per_page(&state, query.per_page);
+
+  match Todo::trashed(&state.get_connection(), page, per_page, &auth.id) {
+    Ok(paginated) => HttpResponse::Ok().json(paginated),
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
@@ -10,12 +10,26 @@ use actix_web::{web, HttpResponse, Responder};
 /// {
 ///   "id": "be24fb8b-09ca-472c-abef-4ae04c530cfd",
 ///   "email": "test@barrage.net",
+///   "created_at": "2020-10-19T11:00:00Z",
+///   "updated_at": "2020-10-19T11:00:00Z",
 ///   "todos": [
 ///     {
 ///       "id": "06b8ff8c-3e34-4226-b917-cb07bd98785e",
 ///       "user_id": "c4d5f3b2-5149-489e-b103-f9e3b8adc3ff",
 ///       "content": "Do something",
-///       "checked": true
+///       "checked": true,
+///       "created_at": "2020-10-20T09:30:00Z",
+///       "updated_at": "2020-10-20T10:15:00Z",
+///       "completed_at": "2020-10-20T10:15:00Z",
+///       "due_at": null,
+///       "priority": "normal",
+///       "position": 1.0,
+///       "list_id": null,
+///       "parent_id": null,
+///       "recurrence": null,
+///       "series_id": null,
+///       "deleted_at": null,
+///       "assignee_id": null
 ///     }
 ///   ]
 /// }
@@ -0,0 +1,45 @@
+use crate::middleware::auth::TokenId;
+use crate::models::auth::AuthenticableUser;
+use crate::models::user::User;
+use crate::state::app::AppState;
+use crate::validation::change_password_request::ChangePasswordRequest;
+use actix_web::{web, HttpResponse, Responder};
+use actix_web_validator::Json;
+
+/// Change your password, the old password has to be given as well.
+/// All the other sessions are logged out, the current one stays active.
+///
+/// @param {String} old_password
+/// @param {String} new_password
+///
+/// Success code 204
+///
+/// Error: 400 or 403
+pub async fn handle(
+  req: web::HttpRequest,
+  data: Json<ChangePasswordRequest>,
+  state: web::Data<AppState>,
+) -> impl Responder {
+  let auth = match req.extensions_mut().remove::<User>() {
+    Some(user) => user,
+    None => return HttpResponse::BadRequest().finish(),
+  };
+
+  let token_id = req.extensions_mut().remove::<TokenId>();
+  let connection = &state.get_connection();
+
+  let user = match AuthenticableUser::authenticate(connection, &auth.email, &data.old_password) {
+    Ok(user) => user,
+    Err(_) => return HttpResponse::Forbidden().finish(),
+  };
+
+  let current_jti = token_id.as_ref().map(|token_id| token_id.0.as_str());
+
+  match user.change_password(connection, &data.new_password, current_jti) {
+    Ok(_) => {
+      state.users().forget(&user.id);
+      HttpResponse::NoContent().finish()
+    }
+    Err(_) => HttpResponse::BadRequest().finish(),
+  }
+}
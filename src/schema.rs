@@ -1,9 +1,116 @@
+table! {
+    attachments (id) {
+        id -> Varchar,
+        todo_id -> Varchar,
+        user_id -> Varchar,
+        filename -> Varchar,
+        content_type -> Varchar,
+        size -> Int8,
+        storage_key -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    comments (id) {
+        id -> Varchar,
+        todo_id -> Varchar,
+        user_id -> Varchar,
+        content -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    list_members (list_id, user_id) {
+        list_id -> Varchar,
+        user_id -> Varchar,
+        role -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    lists (id) {
+        id -> Varchar,
+        user_id -> Varchar,
+        name -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    password_resets (id) {
+        id -> Varchar,
+        user_id -> Varchar,
+        token_hash -> Varchar,
+        expires_at -> Timestamptz,
+        used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    refresh_tokens (id) {
+        id -> Varchar,
+        user_id -> Varchar,
+        token_hash -> Varchar,
+        jti -> Varchar,
+        expires_at -> Timestamptz,
+        revoked_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    tags (id) {
+        id -> Varchar,
+        user_id -> Varchar,
+        name -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    todo_events (id) {
+        id -> Varchar,
+        todo_id -> Varchar,
+        actor_id -> Nullable<Varchar>,
+        kind -> Varchar,
+        old_value -> Nullable<Jsonb>,
+        new_value -> Nullable<Jsonb>,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    todo_tags (todo_id, tag_id) {
+        todo_id -> Varchar,
+        tag_id -> Varchar,
+    }
+}
+
 table! {
     todos (id) {
         id -> Varchar,
         user_id -> Varchar,
         content -> Text,
         checked -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        completed_at -> Nullable<Timestamptz>,
+        due_at -> Nullable<Timestamptz>,
+        reminded_at -> Nullable<Timestamptz>,
+        priority -> Int2,
+        position -> Float8,
+        list_id -> Nullable<Varchar>,
+        parent_id -> Nullable<Varchar>,
+        recurrence -> Nullable<Varchar>,
+        series_id -> Nullable<Varchar>,
+        deleted_at -> Nullable<Timestamptz>,
+        assignee_id -> Nullable<Varchar>,
     }
 }
 
@@ -12,9 +119,38 @@ table! {
         id -> Varchar,
         email -> Varchar,
         password -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
+joinable!(attachments -> todos (todo_id));
+joinable!(attachments -> users (user_id));
+joinable!(comments -> todos (todo_id));
+joinable!(comments -> users (user_id));
+joinable!(list_members -> lists (list_id));
+joinable!(list_members -> users (user_id));
+joinable!(lists -> users (user_id));
+joinable!(password_resets -> users (user_id));
+joinable!(refresh_tokens -> users (user_id));
+joinable!(tags -> users (user_id));
+joinable!(todo_events -> todos (todo_id));
+joinable!(todo_events -> users (actor_id));
+joinable!(todo_tags -> tags (tag_id));
+joinable!(todo_tags -> todos (todo_id));
+joinable!(todos -> lists (list_id));
 joinable!(todos -> users (user_id));
 
-allow_tables_to_appear_in_same_query!(todos, users,);
+allow_tables_to_appear_in_same_query!(
+    attachments,
+    comments,
+    list_members,
+    lists,
+    password_resets,
+    refresh_tokens,
+    tags,
+    todo_events,
+    todo_tags,
+    todos,
+    users,
+);
@@ -0,0 +1,91 @@
+use crate::models::list::List;
+use crate::models::list_member::{ListMember, Role};
+use crate::models::todo::Todo;
+use diesel::result;
+
+/// What the user is about to do with the list or the todo
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Access {
+  /// Read the list or the todo
+  View,
+  /// Check or uncheck the todo
+  Complete,
+  /// Change the todos, e.g. edit, check, move or delete them
+  Edit,
+  /// Change the list itself, e.g. rename it or share it with others
+  Manage,
+}
+
+/// Whether the role on the list is enough for the access
+pub fn role_allows(role: Role, access: Access) -> bool {
+  match access {
+    Access::View => true,
+    Access::Complete | Access::Edit => role >= Role::Editor,
+    Access::Manage => role == Role::Owner,
+  }
+}
+
+/// Role of the user on the list, the user who created the list is always
+/// its owner. None is returned when the list wasn't shared with the user.
+pub fn list_role(
+  connection: &crate::diesel::PgConnection,
+  list: &List,
+  user_id: &str,
+) -> Result<Option<Role>, result::Error> {
+  if list.user_id == user_id {
+    return Ok(Some(Role::Owner));
+  }
+
+  ListMember::role_of(connection, &list.id, user_id)
+}
+
+/// Whether the user is allowed the access on the list
+pub fn can_access_list(
+  connection: &crate::diesel::PgConnection,
+  list: &List,
+  user_id: &str,
+  access: Access,
+) -> Result<bool, result::Error> {
+  Ok(list_role(connection, list, user_id)?.is_some_and(|role| role_allows(role, access)))
+}
+
+/// Whether the user is allowed the access on the todo. Users can do
+/// anything with their own todos and can view and complete the todos
+/// assigned to them. Todos on the list are reachable through the role on
+/// the list, even for the users that created them, so taking the user off
+/// the list takes away the access on the todos the user added to it.
+pub fn can_access_todo(
+  connection: &crate::diesel::PgConnection,
+  todo: &Todo,
+  user_id: &str,
+  access: Access,
+) -> Result<bool, result::Error> {
+  let assigned = todo.assignee_id.as_deref() == Some(user_id);
+  if assigned && matches!(access, Access::View | Access::Complete) {
+    return Ok(true);
+  }
+
+  match &todo.list_id {
+    Some(list_id) => {
+      let list = List::show(connection, list_id)?;
+      can_access_list(connection, &list, user_id, access)
+    }
+    None => Ok(todo.user_id == user_id),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{role_allows, Access};
+  use crate::models::list_member::Role;
+  #[test]
+  fn allow_access_by_role() {
+    assert!(role_allows(Role::Viewer, Access::View));
+    assert!(!role_allows(Role::Viewer, Access::Complete));
+    assert!(!role_allows(Role::Viewer, Access::Edit));
+    assert!(role_allows(Role::Editor, Access::Complete));
+    assert!(role_allows(Role::Editor, Access::Edit));
+    assert!(!role_allows(Role::Editor, Access::Manage));
+    assert!(role_allows(Role::Owner, Access::Manage));
+  }
+}
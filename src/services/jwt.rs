@@ -1,5 +1,15 @@
 use chrono::prelude::*;
-use jsonwebtoken;
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+
+/// Shortest secret that is accepted for signing the tokens with HS256
+pub const MIN_SECRET_LENGTH: usize = 32;
+
+/// Placeholder secrets that were shipped in the example configuration
+const PLACEHOLDER_SECRETS: &[&str] = &[
+  "my_secret_jwt_secret",
+  "change_me_to_a_random_secret_of_at_least_32_bytes",
+];
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct Claims {
@@ -7,71 +17,237 @@ pub struct Claims {
   pub email: String,
   pub exp: i64,
   pub iat: i64,
+  pub jti: String,
 }
 
-/// Generate JWT for passed User
-pub fn generate(user: &crate::models::user::User) -> String {
-  let secret = match dotenv::var("JWT_SECRET") {
-    Ok(s) => s,
-    Err(_) => "".to_string(),
-  };
+/// Keys and settings used to sign and verify the tokens, loaded once on startup.
+/// Tokens are signed with the current key and carry its id in the `kid` header,
+/// previous keys are kept only for the verification so they can be rotated out.
+pub struct JwtConfig {
+  pub algorithm: Algorithm,
+  pub kid: Option<String>,
+  pub lifetime: i64,
+  encoding_key: EncodingKey,
+  decoding_keys: Vec<(Option<String>, DecodingKey<'static>)>,
+}
 
-  let duration = match dotenv::var("JWT_LIFETIME_IN_SECONDS") {
-    Ok(d) => d,
-    Err(_) => "300".to_string(),
-  };
+impl JwtConfig {
+  /// Load the configuration from the environment, `JWT_ALGORITHM` selects
+  /// between the HS256 secret and the RS256 or ES256 keys in PEM files
+  pub fn from_env() -> Result<Self, String> {
+    let lifetime = match dotenv::var("JWT_LIFETIME_IN_SECONDS") {
+      Ok(lifetime) => match lifetime.parse::<i64>() {
+        Ok(lifetime) if lifetime > 0 => lifetime,
+        _ => return Err(String::from("JWT_LIFETIME_IN_SECONDS must be a positive number")),
+      },
+      Err(_) => 300,
+    };
 
-  let duration: i64 = duration.parse().unwrap();
-  let exp = Utc::now() + chrono::Duration::seconds(duration);
+    let kid = dotenv::var("JWT_KEY_ID").ok().filter(|kid| !kid.is_empty());
+    let algorithm = dotenv::var("JWT_ALGORITHM").unwrap_or_else(|_| "HS256".into());
 
-  let claims = Claims {
-    sub: String::from(&user.id),
-    email: String::from(&user.email),
-    exp: exp.timestamp(),
-    iat: Utc::now().timestamp(),
-  };
+    let config = match algorithm.as_str() {
+      "HS256" => Self::hmac(&dotenv::var("JWT_SECRET").unwrap_or_default(), lifetime)?,
+      "RS256" | "ES256" => {
+        let algorithm = if algorithm == "RS256" {
+          Algorithm::RS256
+        } else {
+          Algorithm::ES256
+        };
+
+        let private_key = read_pem("JWT_PRIVATE_KEY_PATH", None)?;
+        let public_key = read_pem("JWT_PUBLIC_KEY_PATH", None)?;
+
+        Self::asymmetric(algorithm, &private_key, &public_key, lifetime)?
+      }
+      _ => return Err(format!("JWT_ALGORITHM {} is not supported", algorithm)),
+    };
+
+    let mut config = config.with_kid(kid);
+
+    // Keys that were rotated out, listed as `kid=path` pairs
+    if let Ok(previous) = dotenv::var("JWT_PREVIOUS_PUBLIC_KEYS") {
+      for key in previous.split(',').filter(|key| !key.trim().is_empty()) {
+        let (kid, path) = match key.trim().split_once('=') {
+          Some((kid, path)) if !kid.is_empty() => (kid, path),
+          _ => return Err(format!("Previous JWT key {} must be given as kid=path", key)),
+        };
+
+        let pem = read_pem("JWT_PREVIOUS_PUBLIC_KEYS", Some(path))?;
+        config = config.with_previous_key(kid, &pem)?;
+      }
+    }
+
+    Ok(config)
+  }
+
+  /// Configuration that signs the tokens with the shared secret,
+  /// empty, placeholder or too short secrets are refused
+  pub fn hmac(secret: &str, lifetime: i64) -> Result<Self, String> {
+    if secret.is_empty() {
+      return Err(String::from("JWT_SECRET is not set"));
+    }
+
+    if PLACEHOLDER_SECRETS.contains(&secret) {
+      return Err(String::from("JWT_SECRET is the example placeholder, set a random secret"));
+    }
+
+    if secret.len() < MIN_SECRET_LENGTH {
+      return Err(format!(
+        "JWT_SECRET is too weak, it has to be at least {} bytes long",
+        MIN_SECRET_LENGTH
+      ));
+    }
+
+    Ok(JwtConfig {
+      algorithm: Algorithm::HS256,
+      kid: None,
+      lifetime,
+      encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+      decoding_keys: vec![(None, DecodingKey::from_secret(secret.as_bytes()).into_static())],
+    })
+  }
+
+  /// Configuration that signs the tokens with the private key and
+  /// verifies them with the public key, both in PEM format
+  pub fn asymmetric(
+    algorithm: Algorithm,
+    private_key: &[u8],
+    public_key: &[u8],
+    lifetime: i64,
+  ) -> Result<Self, String> {
+    let encoding_key = match algorithm {
+      Algorithm::RS256 => EncodingKey::from_rsa_pem(private_key),
+      Algorithm::ES256 => EncodingKey::from_ec_pem(private_key),
+      _ => return Err(format!("{:?} is not an asymmetric algorithm", algorithm)),
+    }
+    .map_err(|e| format!("Invalid JWT private key: {}", e))?;
+
+    Ok(JwtConfig {
+      algorithm,
+      kid: None,
+      lifetime,
+      encoding_key,
+      decoding_keys: vec![(None, decoding_key(algorithm, public_key)?)],
+    })
+  }
+
+  /// Set the id of the current key
+  pub fn with_kid(mut self, kid: Option<String>) -> Self {
+    self.decoding_keys[0].0 = kid.clone();
+    self.kid = kid;
+    self
+  }
 
-  jsonwebtoken::encode(
-    &jsonwebtoken::Header::default(),
-    &claims,
-    &jsonwebtoken::EncodingKey::from_secret(&secret.as_bytes()),
-  )
-  .unwrap_or_default()
+  /// Keep accepting the tokens signed with the previous key
+  pub fn with_previous_key(mut self, kid: &str, public_key: &[u8]) -> Result<Self, String> {
+    let key = decoding_key(self.algorithm, public_key)?;
+    self.decoding_keys.push((Some(String::from(kid)), key));
+
+    Ok(self)
+  }
+
+  /// Generate JWT for passed User, the id of the token is used to revoke it
+  pub fn generate(
+    &self,
+    user: &crate::models::user::User,
+    jti: &str,
+  ) -> Result<String, jsonwebtoken::errors::Error> {
+    let exp = Utc::now() + chrono::Duration::seconds(self.lifetime);
+
+    let claims = Claims {
+      sub: String::from(&user.id),
+      email: String::from(&user.email),
+      exp: exp.timestamp(),
+      iat: Utc::now().timestamp(),
+      jti: String::from(jti),
+    };
+
+    let header = Header {
+      kid: self.kid.clone(),
+      ..Header::new(self.algorithm)
+    };
+
+    jsonwebtoken::encode(&header, &claims, &self.encoding_key)
+  }
+
+  /// Verify given token with the key named in its header and return its claims if its okay
+  pub fn verify(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    let header = jsonwebtoken::decode_header(token)?;
+
+    let key = match header.kid {
+      Some(kid) => self
+        .decoding_keys
+        .iter()
+        .find(|(key_id, _)| key_id.as_deref() == Some(kid.as_str())),
+      None => self.decoding_keys.first(),
+    };
+
+    let key = match key {
+      Some((_, key)) => key,
+      None => return Err(ErrorKind::InvalidToken.into()),
+    };
+
+    let token_data = jsonwebtoken::decode::<Claims>(token, key, &Validation::new(self.algorithm))?;
+
+    Ok(token_data.claims)
+  }
 }
 
-/// Verify given token and return user if its okay
-pub fn verify(token: String) -> Result<crate::models::user::User, jsonwebtoken::errors::Error> {
-  let secret = match dotenv::var("JWT_SECRET") {
-    Ok(s) => s,
-    Err(_) => "".to_string(),
-  };
+/// Parse the public key in PEM format for given algorithm
+fn decoding_key(algorithm: Algorithm, public_key: &[u8]) -> Result<DecodingKey<'static>, String> {
+  match algorithm {
+    Algorithm::RS256 => DecodingKey::from_rsa_pem(public_key),
+    Algorithm::ES256 => DecodingKey::from_ec_pem(public_key),
+    _ => return Err(format!("{:?} is not an asymmetric algorithm", algorithm)),
+  }
+  .map(|key| key.into_static())
+  .map_err(|e| format!("Invalid JWT public key: {}", e))
+}
 
-  let token_data = jsonwebtoken::decode::<Claims>(
-    &token,
-    &jsonwebtoken::DecodingKey::from_secret(secret.as_bytes()),
-    &jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256),
-  )?;
+/// Read the PEM file from the path, or from the path in the variable
+fn read_pem(variable: &str, path: Option<&str>) -> Result<Vec<u8>, String> {
+  let path = match path {
+    Some(path) => String::from(path),
+    None => dotenv::var(variable).map_err(|_| format!("{} is not set", variable))?,
+  };
 
-  Ok(crate::models::user::User::from_jwt(&token_data.claims))
+  std::fs::read(&path).map_err(|e| format!("Could not read {} from {}: {}", variable, path, e))
 }
 
 #[cfg(test)]
 mod tests {
-  use super::{generate, verify};
+  use super::JwtConfig;
   use crate::models::user::User;
   #[test]
   fn generate_and_verify_jwt_token() {
+    let config = JwtConfig::hmac("a-secret-that-is-long-enough-for-hs256", 300)
+      .unwrap()
+      .with_kid(Some("current".into()));
     let user = User {
       id: "123".into(),
-      email: "test@test.com".into(),
-      password: "".into(),
+      ..User::new("test@test.com".into(), "".into())
     };
-    let token = generate(&user.clone());
-    let verify = match verify(token) {
-      Ok(user) => user.id,
+    let token = config.generate(&user.clone(), "456").unwrap();
+    let claims = match config.verify(&token) {
+      Ok(claims) => claims,
       Err(e) => panic!(e),
     };
 
-    assert_eq!(verify, user.id);
+    assert_eq!(claims.sub, user.id);
+    assert_eq!(claims.jti, "456");
+    assert_eq!(jsonwebtoken::decode_header(&token).unwrap().kid, Some("current".into()));
+
+    let rotated = JwtConfig::hmac("a-secret-that-is-long-enough-for-hs256", 300)
+      .unwrap()
+      .with_kid(Some("next".into()));
+    assert!(rotated.verify(&token).is_err());
+  }
+
+  #[test]
+  fn refuse_empty_or_weak_secret() {
+    assert!(JwtConfig::hmac("", 300).is_err());
+    assert!(JwtConfig::hmac("my_secret_jwt_secret", 300).is_err());
+    assert!(JwtConfig::hmac("change_me_to_a_random_secret_of_at_least_32_bytes", 300).is_err());
   }
 }
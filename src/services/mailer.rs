@@ -0,0 +1,54 @@
+use super::outbox;
+use std::path::PathBuf;
+
+/// Mail addressed to the single user
+#[derive(Debug, serde::Serialize)]
+pub struct Mail {
+  pub to: String,
+  pub subject: String,
+  pub body: String,
+}
+
+/// Anything that is able to deliver the mail to the user
+pub trait Mailer: Send + Sync {
+  fn send(&self, mail: &Mail) -> std::io::Result<()>;
+}
+
+/// Mailer that will only write the recipient and the subject of the mail
+/// into the application log, the body is left out since it can contain
+/// secrets like the password reset token.
+pub struct LogMailer;
+
+impl Mailer for LogMailer {
+  fn send(&self, mail: &Mail) -> std::io::Result<()> {
+    println!("Mail to {}: {}", mail.to, mail.subject);
+
+    Ok(())
+  }
+}
+
+/// Mailer that will append each mail as a JSON line into the file,
+/// so it can be picked up and delivered by some other process.
+pub struct FileMailer {
+  pub path: PathBuf,
+}
+
+impl Mailer for FileMailer {
+  fn send(&self, mail: &Mail) -> std::io::Result<()> {
+    outbox::append(&self.path, mail)
+  }
+}
+
+/// Create the mailer configured through the environment
+pub fn from_env() -> Box<dyn Mailer> {
+  let mailer = dotenv::var("MAILER").unwrap_or_else(|_| "log".into());
+
+  match mailer.as_str() {
+    "file" => Box::new(FileMailer {
+      path: dotenv::var("MAILER_FILE_PATH")
+        .unwrap_or_else(|_| "mails.outbox".into())
+        .into(),
+    }),
+    _ => Box::new(LogMailer),
+  }
+}
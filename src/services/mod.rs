@@ -1 +1,8 @@
+pub mod authorization;
 pub mod jwt;
+pub mod mailer;
+pub mod notifier;
+pub mod outbox;
+pub mod storage;
+pub mod token;
+pub mod user_cache;
@@ -0,0 +1,59 @@
+use super::outbox;
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
+
+/// Reminder about the todo that is about to become due, addressed
+/// to the owner of the todo.
+#[derive(Debug, serde::Serialize)]
+pub struct Reminder {
+  pub user_id: String,
+  pub email: String,
+  pub todo_id: String,
+  pub content: String,
+  pub due_at: DateTime<Utc>,
+}
+
+/// Anything that is able to deliver the reminder to the user
+pub trait Notifier: Send + Sync {
+  fn notify(&self, reminder: &Reminder) -> std::io::Result<()>;
+}
+
+/// Notifier that will only write the reminder into the application log
+pub struct LogNotifier;
+
+impl Notifier for LogNotifier {
+  fn notify(&self, reminder: &Reminder) -> std::io::Result<()> {
+    println!(
+      "Reminder: todo {} of {} is due at {}",
+      reminder.todo_id, reminder.email, reminder.due_at
+    );
+
+    Ok(())
+  }
+}
+
+/// Notifier that will append each reminder as a JSON line into the outbox
+/// file, so it can be picked up and delivered by some other process.
+pub struct OutboxNotifier {
+  pub path: PathBuf,
+}
+
+impl Notifier for OutboxNotifier {
+  fn notify(&self, reminder: &Reminder) -> std::io::Result<()> {
+    outbox::append(&self.path, reminder)
+  }
+}
+
+/// Create the notifier configured through the environment
+pub fn from_env() -> Box<dyn Notifier> {
+  let notifier = dotenv::var("REMINDER_NOTIFIER").unwrap_or_else(|_| "log".into());
+
+  match notifier.as_str() {
+    "outbox" => Box::new(OutboxNotifier {
+      path: dotenv::var("REMINDER_OUTBOX_PATH")
+        .unwrap_or_else(|_| "reminders.outbox".into())
+        .into(),
+    }),
+    _ => Box::new(LogNotifier),
+  }
+}
@@ -0,0 +1,35 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+/// Append the item as a JSON line into the outbox file, so it can be picked
+/// up and delivered by some other process. The file is created when missing.
+pub fn append<T: serde::Serialize>(path: &Path, item: &T) -> std::io::Result<()> {
+  let mut line = serde_json::to_string(item)?;
+  line.push('\n');
+
+  OpenOptions::new()
+    .create(true)
+    .append(true)
+    .open(path)?
+    .write_all(line.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::append;
+  use serde_json::json;
+  #[test]
+  fn append_json_lines() {
+    let path = std::env::temp_dir().join(format!("outbox-{}", uuid::Uuid::new_v4()));
+
+    append(&path, &json!({ "id": "123" })).unwrap();
+    append(&path, &json!({ "id": "456" })).unwrap();
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let lines: Vec<&str> = written.lines().collect();
+    assert_eq!(lines, vec!["{\"id\":\"123\"}", "{\"id\":\"456\"}"]);
+  }
+}
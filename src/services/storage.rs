@@ -0,0 +1,87 @@
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Anything that is able to keep the uploaded files under the given key
+pub trait Storage: Send + Sync {
+  fn put(&self, key: &str, bytes: &[u8]) -> io::Result<()>;
+  fn get(&self, key: &str) -> io::Result<Vec<u8>>;
+  fn delete(&self, key: &str) -> io::Result<()>;
+}
+
+/// Storage that will keep the files on the local disk under the root directory
+pub struct LocalStorage {
+  pub root: PathBuf,
+}
+
+impl LocalStorage {
+  /// Resolve the key into the path under the root, keys that would
+  /// escape the root directory are refused.
+  fn path(&self, key: &str) -> io::Result<PathBuf> {
+    let relative = Path::new(key);
+    let safe = relative
+      .components()
+      .all(|component| matches!(component, Component::Normal(_)));
+
+    if key.is_empty() || !safe {
+      return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid storage key"));
+    }
+
+    Ok(self.root.join(relative))
+  }
+}
+
+impl Storage for LocalStorage {
+  fn put(&self, key: &str, bytes: &[u8]) -> io::Result<()> {
+    let path = self.path(key)?;
+
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, bytes)
+  }
+
+  fn get(&self, key: &str) -> io::Result<Vec<u8>> {
+    fs::read(self.path(key)?)
+  }
+
+  fn delete(&self, key: &str) -> io::Result<()> {
+    match fs::remove_file(self.path(key)?) {
+      Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+      result => result,
+    }
+  }
+}
+
+/// Create the storage configured through the environment, for now the
+/// files can only be kept on the local disk
+pub fn from_env() -> Box<dyn Storage> {
+  Box::new(LocalStorage {
+    root: dotenv::var("STORAGE_PATH")
+      .unwrap_or_else(|_| "storage".into())
+      .into(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{LocalStorage, Storage};
+  #[test]
+  fn local_storage_keeps_files_under_root() {
+    let root = std::env::temp_dir().join(format!("storage-{}", uuid::Uuid::new_v4()));
+    let storage = LocalStorage { root: root.clone() };
+
+    storage.put("todo/file", b"content").unwrap();
+    assert_eq!(storage.get("todo/file").unwrap(), b"content".to_vec());
+
+    storage.delete("todo/file").unwrap();
+    assert!(storage.get("todo/file").is_err());
+    assert!(storage.delete("todo/file").is_ok());
+
+    assert!(storage.put("../escaped", b"content").is_err());
+    assert!(storage.put("/absolute", b"content").is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+  }
+}
@@ -0,0 +1,29 @@
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Generate random token that is safe to be put into the URL
+pub fn generate() -> String {
+  let mut bytes = [0u8; 32];
+  rand::thread_rng().fill_bytes(&mut bytes);
+
+  base64::encode_config(bytes, base64::URL_SAFE_NO_PAD)
+}
+
+/// Hash of the token that is kept in the database instead of the token itself
+pub fn hash(token: &str) -> String {
+  format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{generate, hash};
+  #[test]
+  fn tokens_are_random_and_hashed() {
+    let token = generate();
+
+    assert_ne!(token, generate());
+    assert_eq!(hash(&token), hash(&token));
+    assert_eq!(hash(&token).len(), 64);
+    assert_ne!(hash(&token), token);
+  }
+}
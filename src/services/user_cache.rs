@@ -0,0 +1,105 @@
+use crate::models::user::User;
+use diesel::result;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Number of cached users after which the expired entries are dropped
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Users looked up by the authentication, kept for the TTL so not every
+/// request has to hit the database. Users that were not found are cached
+/// as well, so tokens of deleted users are cheap to refuse.
+pub struct UserCache {
+  ttl: Duration,
+  entries: Mutex<HashMap<String, (Instant, Option<User>)>>,
+}
+
+impl UserCache {
+  pub fn new(ttl: Duration) -> Self {
+    UserCache {
+      ttl,
+      entries: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Create the cache with the TTL configured by `USER_CACHE_TTL_IN_SECONDS`,
+  /// zero turns the caching off
+  pub fn from_env() -> Self {
+    let ttl: u64 = dotenv::var("USER_CACHE_TTL_IN_SECONDS")
+      .ok()
+      .and_then(|seconds| seconds.parse().ok())
+      .unwrap_or(60);
+
+    Self::new(Duration::from_secs(ttl))
+  }
+
+  /// Find the user with given id, from the cache if it is still fresh
+  pub fn find(
+    &self,
+    connection: &crate::diesel::PgConnection,
+    id: &str,
+  ) -> Result<Option<User>, result::Error> {
+    if let Some(user) = self.cached(id) {
+      return Ok(user);
+    }
+
+    let user = User::find(connection, id)?;
+    self.remember(id, user.clone());
+
+    Ok(user)
+  }
+
+  /// Drop the cached user so the next lookup goes to the database
+  pub fn forget(&self, id: &str) {
+    self.entries.lock().unwrap().remove(id);
+  }
+
+  fn cached(&self, id: &str) -> Option<Option<User>> {
+    match self.entries.lock().unwrap().get(id) {
+      Some((cached_at, user)) if cached_at.elapsed() < self.ttl => Some(user.clone()),
+      _ => None,
+    }
+  }
+
+  fn remember(&self, id: &str, user: Option<User>) {
+    if self.ttl.as_secs() == 0 {
+      return;
+    }
+
+    let mut entries = self.entries.lock().unwrap();
+
+    if entries.len() >= PRUNE_THRESHOLD {
+      let ttl = self.ttl;
+      entries.retain(|_, (cached_at, _)| cached_at.elapsed() < ttl);
+    }
+
+    entries.insert(String::from(id), (Instant::now(), user));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::UserCache;
+  use crate::models::user::User;
+  use std::time::Duration;
+  #[test]
+  fn cache_users_for_the_ttl() {
+    let cache = UserCache::new(Duration::from_secs(60));
+    let user = User::new("test@test.com".into(), "".into());
+
+    assert_eq!(cache.cached(&user.id), None);
+
+    cache.remember(&user.id, Some(user.clone()));
+    cache.remember("missing", None);
+    assert_eq!(cache.cached(&user.id), Some(Some(user.clone())));
+    assert_eq!(cache.cached("missing"), Some(None));
+
+    cache.forget(&user.id);
+    assert_eq!(cache.cached(&user.id), None);
+
+    let disabled = UserCache::new(Duration::from_secs(0));
+    disabled.remember(&user.id, Some(user.clone()));
+    assert_eq!(disabled.cached(&user.id), None);
+  }
+}
@@ -1,8 +1,17 @@
+use crate::services::jwt::JwtConfig;
+use crate::services::mailer::{self, Mailer};
+use crate::services::storage::{self, Storage};
+use crate::services::user_cache::UserCache;
 use crate::state::pool;
 use std::sync::Arc;
 
 pub struct StaticData {
   pub db: pool::DbPool,
+  pub storage: Box<dyn Storage>,
+  pub mailer: Box<dyn Mailer>,
+  pub jwt: Arc<JwtConfig>,
+  pub users: Arc<UserCache>,
+  pub max_per_page: u32,
 }
 
 #[derive(Clone)]
@@ -21,12 +30,48 @@ impl AppState {
       .get()
       .expect("Failed to retrieve DB connection from pool")
   }
+
+  pub fn storage(&self) -> &dyn Storage {
+    self.static_data.storage.as_ref()
+  }
+
+  pub fn mailer(&self) -> &dyn Mailer {
+    self.static_data.mailer.as_ref()
+  }
+
+  pub fn jwt(&self) -> &JwtConfig {
+    self.static_data.jwt.as_ref()
+  }
+
+  pub fn users(&self) -> &UserCache {
+    self.static_data.users.as_ref()
+  }
+
+  pub fn max_per_page(&self) -> u32 {
+    self.static_data.max_per_page
+  }
+}
+
+/// Largest number of items per page, configured by the `MAX_PER_PAGE` variable
+pub fn max_per_page_from_env() -> u32 {
+  dotenv::var("MAX_PER_PAGE")
+    .ok()
+    .and_then(|value| value.parse::<u32>().ok())
+    .unwrap_or(crate::MAX_PER_PAGE)
+    .max(1)
 }
 
-pub fn initialize() -> AppState {
+pub fn initialize(jwt: Arc<JwtConfig>, users: Arc<UserCache>, max_per_page: u32) -> AppState {
   let db_pool = pool::get_connection_pool();
 
   AppState {
-    static_data: Arc::new(StaticData { db: db_pool }),
+    static_data: Arc::new(StaticData {
+      db: db_pool,
+      storage: storage::from_env(),
+      mailer: mailer::from_env(),
+      jwt,
+      users,
+      max_per_page,
+    }),
   }
 }
@@ -0,0 +1,17 @@
+use crate::models::todo::BulkAction;
+use actix_web_validator::JsonConfig;
+
+/// Request struct that will be used to extract data from the request
+/// and to run validation on the extracted data.
+#[derive(serde::Deserialize, validator::Validate)]
+pub struct BulkTodoRequest {
+  #[validate(length(min = 1, max = 100))]
+  pub ids: Vec<String>,
+  #[serde(flatten)]
+  pub action: BulkAction,
+}
+
+// App configuration data that will setup the needed configurations on it.
+pub fn app_data() -> JsonConfig {
+  super::default_app_data::<BulkTodoRequest>()
+}
@@ -1,5 +1,18 @@
+pub mod assign_todo_request;
+pub mod attach_tag_request;
+pub mod bulk_todo_request;
+pub mod change_password_request;
+pub mod comment_request;
+pub mod forgot_password_request;
+pub mod list_request;
+pub mod member_request;
+pub mod move_todo_request;
 pub mod new_todo_request;
 pub mod new_user_request;
+pub mod refresh_token_request;
+pub mod reset_password_request;
+pub mod tag_request;
+pub mod update_todo_request;
 
 use actix_web::error::{Error as ActixError, InternalError};
 use actix_web::FromRequest;
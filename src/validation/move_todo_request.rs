@@ -0,0 +1,24 @@
+use actix_web_validator::JsonConfig;
+
+/// Request struct that will be used to extract data from the request
+/// and to run validation on the extracted data.
+#[derive(serde::Deserialize, validator::Validate)]
+#[validate(schema(function = "has_neighbour"))]
+pub struct MoveTodoRequest {
+  pub after: Option<String>,
+  pub before: Option<String>,
+}
+
+/// Custom function that will verify at least one neighbour was given
+fn has_neighbour(request: &MoveTodoRequest) -> Result<(), validator::ValidationError> {
+  if request.after.is_none() && request.before.is_none() {
+    Err(validator::ValidationError::new("missing_neighbour"))
+  } else {
+    Ok(())
+  }
+}
+
+// App configuration data that will setup the needed configurations on it.
+pub fn app_data() -> JsonConfig {
+  super::default_app_data::<MoveTodoRequest>()
+}
@@ -1,11 +1,48 @@
+use crate::models::priority::Priority;
+use crate::models::recurrence::Recurrence;
 use actix_web_validator::JsonConfig;
+use chrono::{DateTime, Utc};
 
 /// Request struct that will be used to extract data from the request
 /// and to run validation on the extracted data.
 #[derive(serde::Deserialize, validator::Validate)]
+#[validate(schema(function = "recurrence_has_due_date"))]
 pub struct NewTodoRequest {
   #[validate(length(min = 3))]
   pub content: Option<String>,
+  #[validate(custom = "not_in_past")]
+  pub due_at: Option<DateTime<Utc>>,
+  pub priority: Option<Priority>,
+  pub list_id: Option<String>,
+  #[validate(custom = "valid_recurrence")]
+  pub recurrence: Option<String>,
+}
+
+/// Custom function that will verify the due date is not already behind us
+fn not_in_past(due_at: &DateTime<Utc>) -> Result<(), validator::ValidationError> {
+  if *due_at < Utc::now() {
+    Err(validator::ValidationError::new("due_at_in_past"))
+  } else {
+    Ok(())
+  }
+}
+
+/// Custom function that will verify the recurrence rule is supported
+fn valid_recurrence(recurrence: &str) -> Result<(), validator::ValidationError> {
+  match recurrence.parse::<Recurrence>() {
+    Ok(_) => Ok(()),
+    Err(_) => Err(validator::ValidationError::new("invalid_recurrence")),
+  }
+}
+
+/// Custom function that will verify recurring todos have a due date which
+/// the following occurrences are calculated from
+fn recurrence_has_due_date(request: &NewTodoRequest) -> Result<(), validator::ValidationError> {
+  if request.recurrence.is_some() && request.due_at.is_none() {
+    Err(validator::ValidationError::new("recurrence_without_due_at"))
+  } else {
+    Ok(())
+  }
 }
 
 // App configuration data that will setup the needed configurations on it.